@@ -0,0 +1,110 @@
+use bech32::{FromBase32, Variant};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::msg::AddressFormat;
+
+// 检查地址是否符合目标链的地址格式
+pub fn is_valid_address(format: &AddressFormat, address: &str) -> bool {
+    match format {
+        AddressFormat::Evm => is_valid_evm_address(address),
+        AddressFormat::Tron => is_valid_tron_address(address),
+        AddressFormat::Solana => is_valid_solana_address(address),
+        AddressFormat::Bech32 { prefix } => is_valid_bech32_address(prefix, address),
+    }
+}
+
+// 0x开头的20字节十六进制地址，大小写混合时需符合EIP-55校验
+fn is_valid_evm_address(address: &str) -> bool {
+    let hex = match address.strip_prefix("0x") {
+        Some(hex) => hex,
+        None => return false,
+    };
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+
+    // 全小写或全大写的地址不带校验
+    if !hex.chars().any(|c| c.is_ascii_uppercase()) || !hex.chars().any(|c| c.is_ascii_lowercase()) {
+        return true;
+    }
+
+    let hash = Keccak256::digest(hex.to_ascii_lowercase().as_bytes());
+    hex.chars().enumerate().all(|(i, c)| {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
+// base58check编码，版本字节为0x41的21字节地址
+fn is_valid_tron_address(address: &str) -> bool {
+    let bytes = match bs58::decode(address).into_vec() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    if bytes.len() != 25 || bytes[0] != 0x41 {
+        return false;
+    }
+
+    let (payload, checksum) = bytes.split_at(21);
+    let hash = Sha256::digest(&Sha256::digest(payload));
+    hash[..4] == *checksum
+}
+
+// base58编码的32字节公钥
+fn is_valid_solana_address(address: &str) -> bool {
+    match bs58::decode(address).into_vec() {
+        Ok(bytes) => bytes.len() == 32,
+        Err(_) => false,
+    }
+}
+
+// 指定前缀的bech32地址，数据为20字节账户地址或32字节合约地址
+fn is_valid_bech32_address(prefix: &str, address: &str) -> bool {
+    let (hrp, data, variant) = match bech32::decode(address) {
+        Ok(decoded) => decoded,
+        Err(_) => return false,
+    };
+    if hrp != prefix || variant != Variant::Bech32 {
+        return false;
+    }
+
+    match Vec::<u8>::from_base32(&data) {
+        Ok(bytes) => bytes.len() == 20 || bytes.len() == 32,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evm_address() {
+        // EIP-55示例地址
+        assert!(is_valid_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(!is_valid_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(is_valid_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+        assert!(!is_valid_evm_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+    }
+
+    #[test]
+    fn tron_address() {
+        assert!(is_valid_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+        // 同一地址修改校验和
+        assert!(!is_valid_tron_address("TR7NHqjeKQxGTCi8q8ZY4pL8otSzghrjpY"));
+    }
+
+    #[test]
+    fn solana_address() {
+        assert!(is_valid_solana_address("4wBqpZM9xaSheZzJSMawUKKwhdpChKbZ5eu5ky4Vigw"));
+        // 31字节公钥
+        assert!(!is_valid_solana_address("thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE"));
+    }
+
+    #[test]
+    fn bech32_address() {
+        assert!(is_valid_bech32_address("terra", "terra1qqqsyqcyq5rqwzqfpg9scrgwpugpzysn9jt6ne"));
+        assert!(!is_valid_bech32_address("terra", "cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363e"));
+    }
+}
@@ -3,20 +3,67 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     from_binary, to_binary, Binary, QuerierWrapper, QueryRequest, WasmQuery, Deps, DepsMut, Coin,
     Env, Addr, MessageInfo, Response, StdResult, Uint128, CosmosMsg, WasmMsg, StdError, BankMsg,
-    BankQuery, BalanceResponse
+    BankQuery, BalanceResponse, Storage, CanonicalAddr, Decimal, SubMsg, Reply, ContractResult, Uint256,
+    Event, SubMsgExecutionResponse
 };
 use cw2::set_contract_version;
-use cw20::{Cw20ReceiveMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg};
+use cw20::{Cw20ReceiveMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, MinterResponse, TokenInfoResponse};
 
 use crate::error::ContractError;
 use crate::msg::{
     ExecuteMsg, InstantiateMsg, QueryMsg, Order, Cw20HookMsg, QuerySupportTokenResponse,
-    QueryOrderStatusResponse, QuerySupportCoinResponse, QueryConfigResponse
+    QueryOrderStatusResponse, QuerySupportCoinResponse, QueryConfigResponse, QueryUnsweptFeeResponse,
+    QueryRewardsPoolResponse, QueryUnpaidRewardsResponse, FeeTier, QueryFeeTiersResponse,
+    QueryFeeQuoteResponse, QueryFeePriceResponse, QueryFeeBalanceResponse, TokenCapability,
+    QueryTokenCapabilityResponse, QueryLpPoolResponse, PendingDeliveryResponse,
+    QueryPendingDeliveriesResponse, ConfirmOrder, DeliveryMode, QueryClaimableResponse,
+    DeliveryLeg, FailedDeliveryResponse, QueryFailedDeliveriesResponse, QueryCallAllowlistResponse,
+    SwapRoute, QuerySwapPairResponse, AssetMode, QueryWrappedSupplyResponse,
+    ChainDecimals, DustRule, QueryChainDecimalsResponse, QueryRetainedDustResponse,
+    RouteResponse, QueryRoutesResponse, AddressFormat, QueryChainAddressFormatResponse,
+    RefundRequestStatus, QueryRefundConfigResponse, QueryRefundRequestResponse, Checkpoint,
+    QueryLivenessResponse, QueryCheckpointResponse, RefundOrder, DepositOrder, SignedOrder,
+    QueryNonceResponse, QuerySignedOrderDigestResponse, CrossChainResponse, QueryClientOrderResponse,
+    QueryOrderHashResponse, QueryDstOrderByHashResponse, DstOrderResponse, QueryDstOrderResponse
 };
+use crate::address::is_valid_address;
+use crate::decimals::{to_local_amount, to_remote_amount, to_uint128};
+use crate::hash::{order_hash, to_hex};
+use crate::oracle::query_oracle_price;
+use crate::signature::{pubkey_to_canonical, signed_order_digest};
+use crate::pair::{
+    query_pair_info, query_simulation, Asset, AssetInfo, PairCw20HookMsg, PairExecuteMsg
+};
+use crate::tax::deduct_tax;
 use crate::state::{
     Config, read_config, store_config, store_support_token_config, read_support_token_config,
     store_src_order_state, read_src_order_state, store_dst_order_state, read_dst_order_state,
-    read_support_coin_config, store_support_coin_config, read_pause, store_pause
+    read_support_coin_config, store_support_coin_config, read_pause, store_pause,
+    read_accrued_fee, store_accrued_fee, UnpaidRewards, read_rewards_pool, store_rewards_pool,
+    read_unpaid_rewards, store_unpaid_rewards, remove_unpaid_rewards, DailyVolume, read_fee_tiers,
+    store_fee_tiers, read_channel_fee_rate, store_channel_fee_rate, remove_channel_fee_rate,
+    read_sender_volume, store_sender_volume, read_fee_price, store_fee_price, remove_fee_price,
+    read_price_oracle, store_price_oracle, read_fee_balance, store_fee_balance, read_fee_balance_total,
+    store_fee_balance_total, read_token_capability, store_token_capability, LpPool, read_lp_pool,
+    store_lp_pool, read_lp_token_asset, store_lp_token_asset, read_lp_allowlist, store_lp_allowlist,
+    PendingDelivery, read_delivery_queue, store_delivery_queue, read_pending_delivery,
+    store_pending_delivery, remove_pending_delivery, read_claimable, store_claimable,
+    read_claimable_total, store_claimable_total, Delivery, next_reply_id, store_delivery_reply,
+    read_delivery_reply, remove_delivery_reply, store_batch_reply, read_batch_reply, remove_batch_reply,
+    store_failed_delivery, read_failed_delivery,
+    remove_failed_delivery, read_failed_delivery_total, store_failed_delivery_total,
+    store_dst_delivery_failed, remove_dst_delivery_failed, read_dst_delivery_failed,
+    read_call_allowlist, store_call_allowlist, read_swap_pair, store_swap_pair, remove_swap_pair,
+    read_asset_mode, store_asset_mode, read_wrapped_supply, store_wrapped_supply,
+    read_chain_decimals, store_chain_decimals, remove_chain_decimals, read_retained_dust, store_retained_dust,
+    read_route, store_route, remove_route, read_routes, read_chain_address_format,
+    store_chain_address_format, remove_chain_address_format, SrcOrder, store_src_order, read_src_order,
+    RefundConfig, store_refund_config, read_refund_config, RefundRequest, store_refund_request,
+    read_refund_request, ChainCheckpoint, store_heartbeat_timeout, read_heartbeat_timeout,
+    store_last_heartbeat, read_last_heartbeat, store_processed_order, read_processed_order, store_checkpoint, read_checkpoint, store_nonce, read_nonce,
+    next_order_id, store_client_order, read_client_order, store_src_order_hash, read_src_order_hash,
+    store_dst_order_hash, read_dst_order_hash, DstOrder, store_dst_order, read_dst_order, store_dst_src_tx,
+    read_dst_src_tx
 };
 
 // version info for migration info
@@ -25,6 +72,46 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const MAX_CROSS_FEE: Uint128 = Uint128::new(100_000);
 const DENOMINATOR: Uint128 = Uint128::new(2_000_000);
+// 分级手续费统计跨链量的天数
+const VOLUME_WINDOW_DAYS: u64 = 30;
+const SECONDS_PER_DAY: u64 = 86_400;
+// 每次发放待发放队列的默认及最大订单数
+const DEFAULT_DRAIN_LIMIT: u32 = 10;
+const MAX_DRAIN_LIMIT: u32 = 30;
+// 批量确认及批量退款的最大订单数
+const MAX_BATCH_SIZE: usize = 50;
+
+// 用户申请退款的默认等待时间及relayer答复的默认时间窗口
+const DEFAULT_REFUND_TIMEOUT: u64 = 7 * SECONDS_PER_DAY;
+const DEFAULT_REFUND_RESPONSE_WINDOW: u64 = 3 * SECONDS_PER_DAY;
+
+// relayer心跳的默认超时时间
+const DEFAULT_HEARTBEAT_TIMEOUT: u64 = SECONDS_PER_DAY;
+
+// 批量处理时汇总的直接转账：资产、接收方、数量及汇总的各笔发放
+type BatchTransfer = (String, CanonicalAddr, Uint128, Vec<Delivery>);
+
+// 批量处理中汇总的直接转账，及各资产已发出但尚未执行的转账数量
+#[derive(Default)]
+struct BatchContext {
+    transfers: Vec<BatchTransfer>,
+    committed: Vec<(String, Uint128)>,
+}
+
+impl BatchContext {
+    fn committed(&self, asset: &str) -> Uint128 {
+        self.committed.iter().find(|(a, _)| a == asset).map(|(_, amount)| *amount).unwrap_or_default()
+    }
+
+    fn commit(&mut self, asset: &str, amount: Uint128) -> StdResult<()> {
+        match self.committed.iter_mut().find(|(a, _)| a == asset) {
+            Some((_, total)) => *total = total.checked_add(amount)?,
+            None => self.committed.push((asset.to_string(), amount)),
+        }
+
+        Ok(())
+    }
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -66,10 +153,10 @@ pub fn execute(
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::CrossChainCoin {
             order_id, asset, to, amount, gas_fee, cross_chain_fee, rewards,
-            src_chain_id, dst_chain_id, deadline, channel
+            src_chain_id, dst_chain_id, deadline, channel, fee_denom
         } => {
             let order = Order {
-                order_id,
+                client_order_id: order_id,
                 asset,
                 from: info.sender.to_string(),
                 to,
@@ -80,12 +167,79 @@ pub fn execute(
                 src_chain_id,
                 dst_chain_id,
                 deadline,
-                channel
+                channel,
+                fee_denom
             };
             cross_chain_coin(deps, env, info, order)
         },
-        ExecuteMsg::CrossChainCoinConfirm { order_id, asset, to, amount, rewards } => cross_chain_coin_confirm(deps, env, info, order_id, asset, to, amount, rewards),
-        ExecuteMsg::CrossChainTokenConfirm { order_id, asset, to, amount, rewards } => cross_chain_token_confirm(deps, env, info, order_id, asset, to, amount, rewards),
+        ExecuteMsg::CrossChainCoinBatch { asset, orders } => cross_chain_coin_batch(deps, env, info, asset, orders),
+        ExecuteMsg::CrossChainTokenFrom {
+            order_id, asset, to, amount, gas_fee, cross_chain_fee, rewards, src_chain_id, dst_chain_id, deadline, channel, fee_denom
+        } => {
+            let order = Order {
+                client_order_id: order_id,
+                asset,
+                from: info.sender.to_string(),
+                to,
+                amount,
+                gas_fee,
+                cross_chain_fee,
+                rewards,
+                src_chain_id,
+                dst_chain_id,
+                deadline,
+                channel,
+                fee_denom
+            };
+            cross_chain_token_from(deps, env, order)
+        },
+        ExecuteMsg::CrossChainTokenSigned { signed, pubkey, signature } => {
+            cross_chain_token_signed(deps, env, info, signed, pubkey, signature)
+        },
+        ExecuteMsg::CrossChainCoinConfirm {
+            order_id, asset, to, amount, rewards, delivery_mode, payload, swap, remote_amount, src_chain_id, order_hash,
+            src_tx_hash, src_event_index, src_sender
+        } => {
+            let order = ConfirmOrder {
+                order_id,
+                asset,
+                to,
+                amount,
+                rewards,
+                delivery_mode,
+                payload,
+                swap,
+                remote_amount,
+                src_chain_id,
+                order_hash,
+                src_tx_hash,
+                src_event_index,
+                src_sender
+            };
+            cross_chain_coin_confirm(deps, env, info, order)
+        },
+        ExecuteMsg::CrossChainTokenConfirm {
+            order_id, asset, to, amount, rewards, delivery_mode, payload, swap, remote_amount, src_chain_id, order_hash,
+            src_tx_hash, src_event_index, src_sender
+        } => {
+            let order = ConfirmOrder {
+                order_id,
+                asset,
+                to,
+                amount,
+                rewards,
+                delivery_mode,
+                payload,
+                swap,
+                remote_amount,
+                src_chain_id,
+                order_hash,
+                src_tx_hash,
+                src_event_index,
+                src_sender
+            };
+            cross_chain_token_confirm(deps, env, info, order)
+        },
         ExecuteMsg::ChangeGovernor { new_governor } => change_governor(deps, info, new_governor),
         ExecuteMsg::ChangeTreasury { new_treasury } => change_treasury(deps, info, new_treasury),
         ExecuteMsg::ChangeCustodian { new_custodian } => change_custodian(deps, info, new_custodian),
@@ -100,6 +254,51 @@ pub fn execute(
         ExecuteMsg::WithdrawalCoin { asset, amount } => withdrawal_coin(deps, env, info, asset, amount),
         ExecuteMsg::WithdrawalPunishToken { asset, amount } => withdrawal_punish_token(deps, env, info, asset, amount),
         ExecuteMsg::WithdrawalPunishCoin { asset, amount } => withdrawal_punish_coin(deps, env, info, asset, amount),
+        ExecuteMsg::SweepFees { assets } => sweep_fees(deps, info, assets),
+        ExecuteMsg::DepositRewards {} => deposit_rewards_coin(deps, info),
+        ExecuteMsg::WithdrawRewards { asset, amount } => withdraw_rewards(deps, info, asset, amount),
+        ExecuteMsg::PayUnpaidRewards { order_id } => pay_unpaid_rewards(deps, env, info, order_id),
+        ExecuteMsg::SetFeeTiers { asset, tiers } => set_fee_tiers(deps, info, asset, tiers),
+        ExecuteMsg::SetChannelFeeRate { channel, fee_rate } => set_channel_fee_rate(deps, info, channel, fee_rate),
+        ExecuteMsg::SetFeePrice { asset, denom, price } => set_fee_price(deps, info, asset, denom, price),
+        ExecuteMsg::SetPriceOracle { oracle } => set_price_oracle(deps, info, oracle),
+        ExecuteMsg::DepositFeeBalance {} => deposit_fee_balance(deps, info),
+        ExecuteMsg::WithdrawFeeBalance { denom, amount } => withdraw_fee_balance(deps, info, denom, amount),
+        ExecuteMsg::SetTokenCapability { asset, capability } => set_token_capability(deps, info, asset, capability),
+        ExecuteMsg::RegisterLpToken { asset, lp_token } => register_lp_token(deps, env, info, asset, lp_token),
+        ExecuteMsg::SetLpConfig { asset, fee_share, permissionless } => set_lp_config(deps, info, asset, fee_share, permissionless),
+        ExecuteMsg::SetLpAllowlist { asset, provider, allowed } => set_lp_allowlist(deps, info, asset, provider, allowed),
+        ExecuteMsg::ProvideLiquidity { asset } => {
+            // 检查实际转账数量
+            let amount = info.funds.iter().find(|x| x.denom == asset).map(|x| x.amount).unwrap_or_default();
+            if read_support_coin_config(deps.storage, &asset)?.is_none() {
+                return Err(ContractError::NotSupportToken {});
+            }
+            provide_liquidity(deps, env, info.sender, asset, amount)
+        },
+        ExecuteMsg::TopUpLiquidity {} => top_up_liquidity_coin(deps, env, info),
+        ExecuteMsg::ProcessQueue { asset, limit } => process_queue(deps, env, asset, limit),
+        ExecuteMsg::Claim { assets } => claim(deps, info, assets),
+        ExecuteMsg::RetryDelivery { order_id, leg } => retry_delivery(deps, env, info, order_id, leg),
+        ExecuteMsg::SetCallAllowlist { contract, allowed } => set_call_allowlist(deps, info, contract, allowed),
+        ExecuteMsg::SetSwapPair { pair, enabled } => set_swap_pair(deps, info, pair, enabled),
+        ExecuteMsg::SwapDelivery { swap, asset, amount, to } => swap_delivery(deps, env, info, swap, asset, amount, to),
+        ExecuteMsg::AssertMinimumReceive { asset_info, prev_balance, minimum_receive, receiver } => {
+            assert_minimum_receive(deps, env, info, asset_info, prev_balance, minimum_receive, receiver)
+        },
+        ExecuteMsg::SetAssetMode { asset, mode, supply_cap } => set_asset_mode(deps, env, info, asset, mode, supply_cap),
+        ExecuteMsg::SetChainDecimals { asset, chain_id, decimals } => set_chain_decimals(deps, info, asset, chain_id, decimals),
+        ExecuteMsg::SetRoute { asset, chain_id, remote_asset } => set_route(deps, info, asset, chain_id, remote_asset),
+        ExecuteMsg::SetChainAddressFormat { chain_id, format } => set_chain_address_format(deps, info, chain_id, format),
+        ExecuteMsg::SetRefundConfig { timeout, response_window } => set_refund_config(deps, info, timeout, response_window),
+        ExecuteMsg::RequestRefund { order_id } => request_refund(deps, env, info, order_id),
+        ExecuteMsg::RespondRefund { order_id, delivered, proof } => respond_refund(deps, env, info, order_id, delivered, proof),
+        ExecuteMsg::ExecuteRefund { order_id } => execute_refund(deps, env, order_id),
+        ExecuteMsg::SetHeartbeatTimeout { timeout } => set_heartbeat_timeout(deps, info, timeout),
+        ExecuteMsg::Heartbeat { checkpoint, delivered, processed } => heartbeat(deps, env, info, checkpoint, delivered, processed),
+        ExecuteMsg::EmergencyReclaim { order_id } => emergency_reclaim(deps, env, info, order_id),
+        ExecuteMsg::BatchConfirm { orders, atomic } => batch_confirm(deps, env, info, orders, atomic),
+        ExecuteMsg::BatchRefund { orders, atomic } => batch_refund(deps, env, info, orders, atomic),
     }
 }
 
@@ -237,23 +436,87 @@ pub fn set_pause_state(deps: DepsMut, info: MessageInfo, state: Uint128) -> Resu
 }
 
 pub fn cross_chain_coin(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     order: Order,
 ) -> Result<Response, ContractError> {
     assert_not_pause(&deps)?;
+    assert_not_emergency(&deps, &env)?;
     // 检查实际转账数量与参数数量是否一致
     assert_sent_coin_balance(&info, &order.asset, &order.amount)?;
 
-    // 获取配置
-    let config: Config = read_config(deps.storage)?;
+    // 随交易发送的手续费币种存入用户的手续费余额
+    if let Some(fee_denom) = &order.fee_denom {
+        credit_sent_fee_balance(&mut deps, &info, &order.asset, fee_denom)?;
+    }
+
+    let (response, order_id) = deposit_coin_order(&mut deps, &env, order, false)?;
+
+    Ok(response.set_data(to_binary(&CrossChainResponse { order_ids: vec![order_id] })?))
+}
+
+// 一笔交易提交多个Coin跨链订单，随交易发送的数量需等于订单数量之和
+pub fn cross_chain_coin_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: String,
+    orders: Vec<DepositOrder>,
+) -> Result<Response, ContractError> {
+    assert_not_pause(&deps)?;
+    assert_not_emergency(&deps, &env)?;
+    assert_batch_size(orders.len())?;
+
+    // 检查实际转账数量与订单数量之和是否一致
+    let total = sum_deposit_amount(&orders)?;
+    assert_sent_coin_balance(&info, &asset, &total)?;
+
+    // 随交易发送的手续费币种存入用户的手续费余额，每个币种只存入一次
+    let mut fee_denoms: Vec<String> = vec![];
+    for fee_denom in orders.iter().filter_map(|order| order.fee_denom.clone()) {
+        if !fee_denoms.contains(&fee_denom) {
+            credit_sent_fee_balance(&mut deps, &info, &asset, &fee_denom)?;
+            fee_denoms.push(fee_denom);
+        }
+    }
+
+    let mut response = Response::new().add_attribute("method", "cross_chain_coin_batch");
+    let mut order_ids: Vec<Uint128> = vec![];
+    for deposit in orders {
+        let order = deposit.into_order(asset.clone(), info.sender.to_string());
+        let (order_response, order_id) = deposit_coin_order(&mut deps, &env, order, true)?;
+        response = merge_batch_response(response, order_response);
+        order_ids.push(order_id);
+    }
+
+    // 新到账的资产优先发放待发放队列
+    let (drain_messages, delivered) = drain_delivery_queue(&mut deps, &env, &asset, DEFAULT_DRAIN_LIMIT)?;
+
+    Ok(response
+        .add_attribute("asset", &asset)
+        .add_attribute("from", info.sender.as_str())
+        .add_attribute("amount", total.to_string())
+        .add_attribute("order_ids", order_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(drain_messages)
+        .set_data(to_binary(&CrossChainResponse { order_ids })?)
+    )
+}
+
+// 记录Coin源链订单，deferred为true时由调用方统一检查到账数量及发放待发放队列
+fn deposit_coin_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    order: Order,
+    deferred: bool,
+) -> Result<(Response, Uint128), ContractError> {
     // 获取最小跨链金额
     let amount_min_op = read_support_coin_config(deps.storage, &order.asset)?;
 
-    if read_src_order_state(deps.storage, &order.order_id)?.is_some() {
-        return Err(ContractError::SrcOrderAlreadyExist {});
-    }
+    // 分配订单id，客户端传入的订单id仅作为同一发送方的幂等键
+    let sender = deps.api.addr_canonicalize(order.from.as_str())?;
+    let order_id = assign_order_id(deps.storage, &sender, order.client_order_id)?;
 
     // 币种检查，不支持的币种拒绝接收
     if amount_min_op.is_none() {
@@ -267,8 +530,22 @@ pub fn cross_chain_coin(
         return Err(ContractError::LessThenAmountMin {});
     }
 
-    // 检查手续费是否超过最大值
-    if order.cross_chain_fee > order.amount.checked_mul(MAX_CROSS_FEE).unwrap().checked_div(DENOMINATOR).unwrap() {
+    // 目标链需已登记对应的资产
+    let remote_asset = match read_route(deps.storage, &order.asset, order.dst_chain_id)? {
+        Some(remote_asset) => remote_asset,
+        None => return Err(ContractError::RouteNotExist {}),
+    };
+
+    // 接收方地址需符合目标链的地址格式
+    assert_dst_address(deps, &order)?;
+
+    // 检查手续费支付币种
+    assert_fee_denom(deps, &order)?;
+
+    // 检查手续费是否超过用户分级及渠道商对应的最大值
+    let now = env.block.time.seconds();
+    let (_, fee_rate) = read_fee_rate(deps.storage, &sender, &order.asset, &order.channel, now)?;
+    if order.cross_chain_fee > order.amount.multiply_ratio(fee_rate, DENOMINATOR) {
         return Err(ContractError::ExceedMaxCrossChainFee {});
     }
 
@@ -277,54 +554,141 @@ pub fn cross_chain_coin(
         return Err(ContractError::ExceedDeadline {});
     }
 
-    // 获取当前合约底仓资产余额
-    let balance = query_balance(
-        &deps.querier,
-        env.contract.address,
-        order.asset.clone()
-    )?;
-
     // 计算收取的费用
-    let fee = order.gas_fee.checked_add(order.cross_chain_fee).unwrap();
-
-    let balance_before = balance.checked_sub(order.amount).unwrap();
-    let balance_after = balance.checked_sub(fee).unwrap();
+    let fee = order.gas_fee.checked_add(order.cross_chain_fee)?;
 
     // 设置订单状态为已完成
-    store_src_order_state(deps.storage, &order.order_id, &Uint128::from(1u128))?;
-
-    // 转账
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let transfer_coin = Coin {
-        denom: order.asset.clone(),
-        amount: fee,
+    store_src_order_state(deps.storage, &order_id, &Uint128::from(1u128))?;
+    store_src_order(deps.storage, &order_id, &SrcOrder {
+        asset: order.asset.clone(),
+        from: sender.clone(),
+        amount: order.amount,
+        gas_fee: order.gas_fee,
+        deadline: order.deadline,
+        burned: Uint128::zero()
+    })?;
+    // 按与EVM合约一致的编码计算订单哈希
+    let hash = Binary::from(order_hash(order_id, &order));
+    store_src_order_hash(deps.storage, &order_id, &hash)?;
+
+    // 累计手续费，指定手续费币种时从用户预存的手续费余额中扣除
+    let (fee_asset, fee_paid) = match &order.fee_denom {
+        Some(fee_denom) => {
+            let price = query_fee_price_rate(deps.as_ref(), &order.asset, fee_denom)?;
+            let fee_paid = fee * price;
+            charge_fee_balance(deps.storage, &sender, fee_denom, fee_paid)?;
+            (fee_denom.clone(), fee_paid)
+        }
+        None => (order.asset.clone(), fee),
     };
-    // 手续费转入relayer
-    messages.push(CosmosMsg::Bank(BankMsg::Send {
-        to_address: deps.api.addr_humanize(&config.relayer)?.to_string(),
-        amount: vec![transfer_coin],
-    }));
+    // LP按比例分得跨链手续费，计入流动性
+    let lp_fee = if fee_asset == order.asset {
+        share_lp_fee(deps.storage, &order.asset, order.cross_chain_fee)?
+    } else {
+        Uint128::zero()
+    };
+    let accrued_fee = read_accrued_fee(deps.storage, &fee_asset)?.checked_add(fee_paid.checked_sub(lp_fee)?)?;
+    store_accrued_fee(deps.storage, &fee_asset, &accrued_fee)?;
 
-    Ok(Response::new()
+    // 按目标链精度换算跨出数量
+    let bridged = if fee_asset == order.asset { order.amount.checked_sub(fee_paid)? } else { order.amount };
+    let (remote_amount, dust) = convert_src_amount(deps.storage, &order.asset, order.dst_chain_id, bridged)?;
+
+    // 记录用户跨链量
+    record_sender_volume(deps.storage, &sender, &order.asset, order.amount, now)?;
+
+    let mut response = Response::new()
         .add_attribute("method", "cross_chain_coin")
-        .add_attribute("order_id", &order.order_id.to_string())
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("client_order_id", order.client_order_id.map(|id| id.to_string()).unwrap_or_default())
+        .add_attribute("order_hash", to_hex(hash.as_slice()))
         .add_attribute("asset", &order.asset)
         .add_attribute("from", &order.from)
         .add_attribute("to", &order.to)
         .add_attribute("amount", &order.amount.to_string())
         .add_attribute("fee", &fee.to_string())
+        .add_attribute("fee_denom", &fee_asset)
+        .add_attribute("fee_paid", fee_paid.to_string())
+        .add_attribute("lp_fee", lp_fee.to_string())
         .add_attribute("rewards", &order.rewards.to_string())
         .add_attribute("src_chain_id", &order.src_chain_id.to_string())
         .add_attribute("dst_chain_id", &order.dst_chain_id.to_string())
         .add_attribute("channel", &order.channel)
-        .add_attribute("balance_before", &balance_before.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
-    )
+        .add_attribute("remote_asset", &remote_asset)
+        .add_attribute("remote_amount", remote_amount.to_string())
+        .add_attribute("dust", dust.to_string());
+
+    if !deferred {
+        // 获取当前合约底仓资产余额，手续费留存在合约中，由treasury批量划转
+        let balance = query_balance(
+            &deps.querier,
+            env.contract.address.clone(),
+            order.asset.clone()
+        )?;
+        let balance_before = balance.checked_sub(order.amount)?;
+
+        // 新到账的资产优先发放待发放队列
+        let (drain_messages, delivered) = drain_delivery_queue(deps, env, &order.asset, DEFAULT_DRAIN_LIMIT)?;
+        response = response
+            .add_attribute("balance_before", &balance_before.to_string())
+            .add_attribute("balance_after", &balance.to_string())
+            .add_attribute("queue_delivered", delivered.join(","))
+            .add_submessages(drain_messages);
+    }
+
+    Ok((response, order_id))
+}
+
+// 分配全局递增的订单id并跳过已使用的id，同一发送方重复提交相同的幂等键时拒绝
+fn assign_order_id(
+    storage: &mut dyn Storage,
+    sender: &CanonicalAddr,
+    client_order_id: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+    if let Some(client_order_id) = &client_order_id {
+        if read_client_order(storage, sender, client_order_id)?.is_some() {
+            return Err(ContractError::SrcOrderAlreadyExist {});
+        }
+    }
+
+    let mut order_id = next_order_id(storage)?;
+    while read_src_order_state(storage, &order_id)?.is_some() {
+        order_id = next_order_id(storage)?;
+    }
+
+    if let Some(client_order_id) = &client_order_id {
+        store_client_order(storage, sender, client_order_id, &order_id)?;
+    }
+
+    Ok(order_id)
+}
+
+// 随交易发送的手续费币种存入用户的手续费余额
+fn credit_sent_fee_balance(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    asset: &str,
+    fee_denom: &str,
+) -> StdResult<()> {
+    if let Some(coin) = info.funds.iter().find(|x| x.denom == fee_denom && x.denom != asset) {
+        let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+        credit_fee_balance(deps.storage, &sender, &coin.denom, coin.amount)?;
+    }
+
+    Ok(())
+}
+
+fn sum_deposit_amount(orders: &[DepositOrder]) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for order in orders {
+        total = total.checked_add(order.amount)?;
+    }
+
+    Ok(total)
 }
 
 pub fn refund_coin(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     order_id: Uint128,
@@ -335,116 +699,338 @@ pub fn refund_coin(
 ) -> Result<Response, ContractError> {
     assert_relayer(&deps, &info)?;
 
-    let refund_to = deps.api.addr_canonicalize(from.as_str())?;
+    let refund = RefundOrder { order_id, asset, from, amount, gas_fee };
+    assert_refund_order(&deps, &refund)?;
+
+    refund_coin_order(&mut deps, &env, refund, None)
+}
+
+// 退还Coin源链订单，批量退款时转账汇总到batch中统一发放
+fn refund_coin_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    refund: RefundOrder,
+    batch: Option<&mut BatchContext>,
+) -> Result<Response, ContractError> {
+    let refund_to = deps.api.addr_canonicalize(refund.from.as_str())?;
+
+    // 发送交易收取的gas费用
+    let amount_sub_gas = refund.amount.checked_sub(refund.gas_fee)?;
+
+    // 设置订单状态为已退款
+    store_src_order_state(deps.storage, &refund.order_id, &Uint128::from(2u128))?;
+
+    let mut response = Response::new()
+        .add_attribute("method", "refund_coin")
+        .add_attribute("order_id", &refund.order_id.to_string())
+        .add_attribute("asset", &refund.asset)
+        .add_attribute("from", &refund.from)
+        .add_attribute("amount", &refund.amount.to_string())
+        .add_attribute("gas_fee", &refund.gas_fee.to_string());
+
+    match batch {
+        Some(batch) => add_batch_transfer(batch, Delivery {
+            order_id: refund.order_id,
+            leg: DeliveryLeg::Refund,
+            asset: refund.asset.clone(),
+            to: refund_to,
+            amount: amount_sub_gas,
+            payload: None,
+            swap: None
+        })?,
+        None => {
+            // 获取当前合约底仓资产余额
+            let balance = query_balance(
+                &deps.querier,
+                env.contract.address.clone(),
+                refund.asset.clone()
+            )?;
+            let balance_after = balance.checked_sub(amount_sub_gas)?;
+
+            // 扣除稳定费
+            let (message, tax) = build_transfer_msg(deps.as_ref(), &refund.asset, &deps.api.addr_humanize(&refund_to)?, amount_sub_gas)?;
+            response = response
+                .add_attribute("tax", tax.to_string())
+                .add_attribute("balance_before", &balance.to_string())
+                .add_attribute("balance_after", &balance_after.to_string())
+                .add_message(message);
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn cross_chain_coin_confirm(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut order: ConfirmOrder,
+) -> Result<Response, ContractError> {
+    assert_relayer(&deps, &info)?;
+    assert_confirm_order(&deps, &mut order)?;
+
+    confirm_coin_order(&mut deps, &env, order, None)
+}
+
+// 发放Coin目标链订单，批量确认时直接转账的部分汇总到batch中统一发放
+fn confirm_coin_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    order: ConfirmOrder,
+    batch: Option<&mut BatchContext>,
+) -> Result<Response, ContractError> {
+    let confirm_to = deps.api.addr_canonicalize(order.to.as_str())?;
 
-    if read_src_order_state(deps.storage, &order_id)?.is_none() {
-        return Err(ContractError::SrcOrderNotExist {});
+    // 设置订单状态为已完成
+    record_dst_order(deps.storage, env, &order, &confirm_to)?;
+
+    // 底仓不足或已有待发放订单时进入待发放队列，批量确认时需计入本批已发出的转账
+    let committed = batch.as_ref().map(|batch| batch.committed(&order.asset)).unwrap_or_default();
+    if should_queue_delivery(deps.as_ref(), env, &order.asset, order.amount.checked_add(committed)?)? {
+        return queue_delivery(deps, env, "cross_chain_coin_confirm", batch.is_none(), PendingDelivery {
+            order_id: order.order_id,
+            asset: order.asset,
+            to: confirm_to,
+            amount: order.amount,
+            rewards: order.rewards,
+            delivery_mode: order.delivery_mode,
+            payload: order.payload,
+            swap: order.swap
+        });
     }
 
-    // 检查订单状态
-    if read_src_order_state(deps.storage, &order_id)?.unwrap() != Uint128::from(1u128) {
-        return Err(ContractError::SrcOrderNotSuccess {});
+    let batched = batch.is_some();
+    let mut messages: Vec<SubMsg> = vec![];
+    // 从返佣资金池支付返佣
+    let rewards_paid = pay_rewards_from_pool(deps.storage, &order.order_id, &order.asset, &confirm_to, order.rewards)?;
+    let rewards_unpaid = order.rewards.checked_sub(rewards_paid)?;
+    let transfer_amount = order.amount.checked_add(rewards_paid)?;
+
+    let mut tax = Uint128::zero();
+    match order.delivery_mode {
+        // 记入接收方的待领取余额
+        DeliveryMode::Credit => credit_claimable(deps.storage, &confirm_to, &order.asset, transfer_amount)?,
+        // 批量确认时本金与返佣汇总后直接转账
+        DeliveryMode::Push => match batch {
+            Some(batch) if order.payload.is_none() && order.swap.is_none() => {
+                add_order_batch_transfers(batch, Delivery {
+                    order_id: order.order_id,
+                    leg: DeliveryLeg::Principal,
+                    asset: order.asset.clone(),
+                    to: confirm_to.clone(),
+                    amount: order.amount,
+                    payload: None,
+                    swap: None
+                }, rewards_paid)?;
+                batch.commit(&order.asset, transfer_amount)?;
+            },
+            // 本金与返佣分别转账给接收方，扣除稳定费
+            batch => {
+                let (order_messages, order_tax) = build_order_submsgs(deps, env, Delivery {
+                    order_id: order.order_id,
+                    leg: DeliveryLeg::Principal,
+                    asset: order.asset.clone(),
+                    to: confirm_to.clone(),
+                    amount: order.amount,
+                    payload: order.payload.clone(),
+                    swap: order.swap.clone()
+                }, rewards_paid)?;
+                messages = order_messages;
+                tax = order_tax;
+                if let Some(batch) = batch {
+                    batch.commit(&order.asset, transfer_amount)?;
+                }
+            }
+        }
     }
 
-    // 获取当前合约底仓资产余额
-    let balance = query_balance(
-        &deps.querier,
-        env.contract.address,
-        asset.clone()
-    )?;
+    let mut response = Response::new()
+        .add_attribute("method", "cross_chain_coin_confirm")
+        .add_attribute("order_id", &order.order_id.to_string())
+        .add_attribute("order_hash", order.order_hash.as_ref().map(|hash| to_hex(hash.as_slice())).unwrap_or_default())
+        .add_attribute("src_tx_hash", order.src_tx_hash.clone().unwrap_or_default())
+        .add_attribute("asset", &order.asset)
+        .add_attribute("to", &order.to)
+        .add_attribute("amount", &order.amount.to_string())
+        .add_attribute("rewards", &order.rewards.to_string())
+        .add_attribute("rewards_paid", rewards_paid.to_string())
+        .add_attribute("rewards_unpaid", rewards_unpaid.to_string())
+        .add_attribute("delivery_mode", format!("{:?}", order.delivery_mode))
+        .add_attribute("tax", tax.to_string());
+
+    // 批量确认时不逐笔查询底仓余额
+    if !batched {
+        // 获取当前合约底仓资产余额
+        let balance = query_balance(
+            &deps.querier,
+            env.contract.address.clone(),
+            order.asset.clone()
+        )?;
+        let balance_after = balance.checked_sub(transfer_amount)?;
+        response = response
+            .add_attribute("balance_before", &balance.to_string())
+            .add_attribute("balance_after", &balance_after.to_string());
+    }
 
-    // 发送交易收取的gas费用
-    let amount_sub_gas = amount.checked_sub(gas_fee).unwrap();
+    Ok(response.add_submessages(messages))
+}
 
-    let balance_after = balance.checked_sub(amount_sub_gas).unwrap();
+// relayer批量确认目标链订单，atomic为false时跳过校验失败的订单并逐笔返回失败原因
+pub fn batch_confirm(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    orders: Vec<ConfirmOrder>,
+    atomic: bool,
+) -> Result<Response, ContractError> {
+    assert_relayer(&deps, &info)?;
+    assert_batch_size(orders.len())?;
+
+    let mut response = Response::new().add_attribute("method", "batch_confirm");
+    let mut batch = BatchContext::default();
+    let mut confirmed: Vec<String> = vec![];
+    let mut failed: Vec<String> = vec![];
+    for mut order in orders {
+        if let Err(err) = assert_confirm_order(&deps, &mut order) {
+            if atomic {
+                return Err(err);
+            }
+            failed.push(order.order_id.to_string());
+            response = response.add_event(batch_failed_event(&order.order_id, err));
+            continue;
+        }
 
-    // 设置订单状态为已退款
-    store_src_order_state(deps.storage, &order_id, &Uint128::from(2u128))?;
+        confirmed.push(order.order_id.to_string());
+        // 支持的Coin按Coin发放，其余按cw20 Token发放
+        let order_response = if read_support_coin_config(deps.storage, &order.asset)?.is_some() {
+            confirm_coin_order(&mut deps, &env, order, Some(&mut batch))?
+        } else {
+            confirm_token_order(&mut deps, &env, order, Some(&mut batch))?
+        };
+        response = merge_batch_response(response, order_response);
+    }
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let transfer_coin = Coin {
-        denom: asset.clone(),
-        amount: amount_sub_gas,
-    };
-    messages.push(CosmosMsg::Bank(BankMsg::Send {
-        to_address: deps.api.addr_humanize(&refund_to)?.to_string(),
-        amount: vec![transfer_coin],
-    }));
+    let (messages, sent) = build_batch_transfers(&mut deps, batch.transfers)?;
 
-    Ok(Response::new()
-        .add_attribute("method", "refund_coin")
-        .add_attribute("order_id", &order_id.to_string())
-        .add_attribute("asset", &asset)
-        .add_attribute("from", &from)
-        .add_attribute("amount", &amount.to_string())
-        .add_attribute("gas_fee", &gas_fee.to_string())
-        .add_attribute("balance_before", &balance.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
+    Ok(response
+        .add_attribute("confirmed", confirmed.join(","))
+        .add_attribute("failed", failed.join(","))
+        .add_attribute("transfers", sent.join(","))
+        .add_submessages(messages)
     )
 }
 
-pub fn cross_chain_coin_confirm(
-    deps: DepsMut,
+// relayer批量退还源链订单，atomic为false时跳过校验失败的订单并逐笔返回失败原因
+pub fn batch_refund(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    order_id: Uint128,
-    asset: String,
-    to: String,
-    amount: Uint128,
-    rewards: Uint128
+    orders: Vec<RefundOrder>,
+    atomic: bool,
 ) -> Result<Response, ContractError> {
-    assert_sent_coin_balance(&info, &asset, &rewards)?;
     assert_relayer(&deps, &info)?;
+    assert_batch_size(orders.len())?;
+
+    let mut response = Response::new().add_attribute("method", "batch_refund");
+    let mut batch = BatchContext::default();
+    let mut refunded: Vec<String> = vec![];
+    let mut failed: Vec<String> = vec![];
+    for refund in orders {
+        if let Err(err) = assert_refund_order(&deps, &refund) {
+            if atomic {
+                return Err(err);
+            }
+            failed.push(refund.order_id.to_string());
+            response = response.add_event(batch_failed_event(&refund.order_id, err));
+            continue;
+        }
 
-    let confirm_to = deps.api.addr_canonicalize(to.as_str())?;
+        refunded.push(refund.order_id.to_string());
+        // 支持的Coin按Coin退还，其余按cw20 Token退还
+        let order_response = if read_support_coin_config(deps.storage, &refund.asset)?.is_some() {
+            refund_coin_order(&mut deps, &env, refund, Some(&mut batch))?
+        } else {
+            refund_token_order(&mut deps, &env, refund, Some(&mut batch))?
+        };
+        response = merge_batch_response(response, order_response);
+    }
 
-    // 检查订单状态
-    if read_dst_order_state(deps.storage, &order_id)?.is_some() {
-        return Err(ContractError::DstOrderAlreadyExist {});
+    let (messages, sent) = build_batch_transfers(&mut deps, batch.transfers)?;
+
+    Ok(response
+        .add_attribute("refunded", refunded.join(","))
+        .add_attribute("failed", failed.join(","))
+        .add_attribute("transfers", sent.join(","))
+        .add_submessages(messages)
+    )
+}
+
+fn assert_batch_size(size: usize) -> Result<(), ContractError> {
+    if size == 0 || size > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize {});
     }
 
-    // 获取当前合约底仓资产余额
-    let balance = query_balance(
-        &deps.querier,
-        env.contract.address,
-        asset.clone()
-    )?;
-    let balance_before = balance.checked_sub(rewards).unwrap();
+    Ok(())
+}
 
-    // 设置订单状态为已完成
-    store_dst_order_state(deps.storage, &order_id, &Uint128::from(env.block.time.nanos()))?;
+// 单笔订单的处理结果作为事件记录，转账消息并入批量处理的Response
+fn merge_batch_response(response: Response, order_response: Response) -> Response {
+    response
+        .add_event(Event::new("batch_order")
+            .add_attribute("status", "success")
+            .add_attributes(order_response.attributes))
+        .add_submessages(order_response.messages)
+}
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let mut transfer_amount = amount;
-    // 是否有返佣
-    if !rewards.is_zero() {
-        transfer_amount = transfer_amount.checked_add(rewards).unwrap();
+fn batch_failed_event(order_id: &Uint128, err: ContractError) -> Event {
+    Event::new("batch_order")
+        .add_attribute("status", "failed")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("error", err.to_string())
+}
+
+// 汇总同一资产、同一接收方的直接转账
+fn add_batch_transfer(batch: &mut BatchContext, delivery: Delivery) -> StdResult<()> {
+    if delivery.amount.is_zero() {
+        return Ok(());
+    }
+
+    match batch.transfers.iter_mut().find(|(asset, to, _, _)| *asset == delivery.asset && *to == delivery.to) {
+        Some((_, _, total, deliveries)) => {
+            *total = total.checked_add(delivery.amount)?;
+            deliveries.push(delivery);
+        },
+        None => batch.transfers.push((delivery.asset.clone(), delivery.to.clone(), delivery.amount, vec![delivery])),
     }
 
-    let balance_after = balance.checked_sub(transfer_amount).unwrap();
+    Ok(())
+}
 
-    // 转账给接收方
-    let transfer_coin = Coin {
-        denom: asset.clone(),
-        amount: transfer_amount,
+// 目标链订单的本金与返佣分别计入汇总转账，转账失败时可分别重试
+fn add_order_batch_transfers(batch: &mut BatchContext, principal: Delivery, rewards: Uint128) -> StdResult<()> {
+    let rewards = Delivery {
+        leg: DeliveryLeg::Rewards,
+        amount: rewards,
+        ..principal.clone()
     };
-    messages.push(CosmosMsg::Bank(BankMsg::Send {
-        to_address: deps.api.addr_humanize(&confirm_to)?.to_string(),
-        amount: vec![transfer_coin],
-    }));
+    add_batch_transfer(batch, principal)?;
+    add_batch_transfer(batch, rewards)
+}
 
-    Ok(Response::new()
-        .add_attribute("method", "cross_chain_coin_confirm")
-        .add_attribute("order_id", &order_id.to_string())
-        .add_attribute("asset", &asset)
-        .add_attribute("to", &to)
-        .add_attribute("amount", &amount.to_string())
-        .add_attribute("rewards", &rewards.to_string())
-        .add_attribute("balance_before", &balance_before.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
-    )
+// 构造汇总后的转账SubMsg，某一接收方转账失败时按其汇总的各笔发放记录失败，不影响其他接收方
+fn build_batch_transfers(deps: &mut DepsMut, transfers: Vec<BatchTransfer>) -> StdResult<(Vec<SubMsg>, Vec<String>)> {
+    let mut messages: Vec<SubMsg> = vec![];
+    let mut sent: Vec<String> = vec![];
+    for (asset, to, amount, deliveries) in transfers {
+        let recipient = deps.api.addr_humanize(&to)?;
+        let (message, tax) = build_transfer_msg(deps.as_ref(), &asset, &recipient, amount)?;
+        let id = next_reply_id(deps.storage)?;
+        store_batch_reply(deps.storage, id, &deliveries)?;
+        messages.push(SubMsg::reply_always(message, id));
+        sent.push(format!("{}:{}:{}:{}", asset, recipient, amount, tax));
+    }
+
+    Ok((messages, sent))
 }
 
 // 提取底仓
@@ -464,18 +1050,20 @@ pub fn withdrawal_coin(
         asset.clone()
     )?;
 
-    // 检查提款是否大于底仓余额
-    if amount > balance {
+    // 检查提款是否大于底仓余额，未划转的手续费、返佣资金池及LP提供的流动性不可提取
+    let reserved = read_reserved_balance(deps.storage, &asset)?.checked_add(read_lp_liquidity(deps.storage, &asset)?)?;
+    if amount > balance.saturating_sub(reserved) {
         return Err(ContractError::NotEnoughBalance {});
     }
 
     let balance_after = balance.checked_sub(amount).unwrap();
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    let transfer_coin = Coin {
+    // 扣除稳定费
+    let (transfer_coin, tax) = deduct_tax(&deps.querier, Coin {
         denom: asset.clone(),
         amount,
-    };
+    })?;
     messages.push(CosmosMsg::Bank(BankMsg::Send {
         to_address: info.sender.to_string(),
         amount: vec![transfer_coin],
@@ -485,6 +1073,7 @@ pub fn withdrawal_coin(
         .add_attribute("method", "withdrawal_coin")
         .add_attribute("asset", &asset)
         .add_attribute("amount", &amount.to_string())
+        .add_attribute("tax", tax.to_string())
         .add_attribute("balance_before", &balance.to_string())
         .add_attribute("balance_after", &balance_after.to_string())
         .add_messages(messages)
@@ -508,18 +1097,20 @@ pub fn withdrawal_punish_coin(
         asset.clone()
     )?;
 
-    // 检查提款是否大于底仓余额
-    if amount > balance {
+    // 检查提款是否大于底仓余额，未划转的手续费、返佣资金池及LP提供的流动性不可提取
+    let reserved = read_reserved_balance(deps.storage, &asset)?.checked_add(read_lp_liquidity(deps.storage, &asset)?)?;
+    if amount > balance.saturating_sub(reserved) {
         return Err(ContractError::NotEnoughBalance {});
     }
 
     let balance_after = balance.checked_sub(amount).unwrap();
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    let transfer_coin = Coin {
+    // 扣除稳定费
+    let (transfer_coin, tax) = deduct_tax(&deps.querier, Coin {
         denom: asset.clone(),
         amount,
-    };
+    })?;
     messages.push(CosmosMsg::Bank(BankMsg::Send {
         to_address: info.sender.to_string(),
         amount: vec![transfer_coin],
@@ -529,6 +1120,7 @@ pub fn withdrawal_punish_coin(
         .add_attribute("method", "withdrawal_punish_coin")
         .add_attribute("asset", &asset)
         .add_attribute("amount", &amount.to_string())
+        .add_attribute("tax", tax.to_string())
         .add_attribute("balance_before", &balance.to_string())
         .add_attribute("balance_after", &balance_after.to_string())
         .add_messages(messages)
@@ -569,10 +1161,10 @@ pub fn receive_cw20(
 ) -> Result<Response, ContractError> {
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::CrossChainToken {
-           order_id, to, gas_fee, cross_chain_fee, rewards, src_chain_id, dst_chain_id, deadline, channel
+           order_id, to, gas_fee, cross_chain_fee, rewards, src_chain_id, dst_chain_id, deadline, channel, fee_denom
         }) => {
             let order = Order {
-                order_id,
+                client_order_id: order_id,
                 asset: info.sender.into(),
                 from: cw20_msg.sender,
                 to,
@@ -583,46 +1175,219 @@ pub fn receive_cw20(
                 src_chain_id,
                 dst_chain_id,
                 deadline,
-                channel
+                channel,
+                fee_denom
             };
             cross_chain_token(deps, env, order)
         },
+        Ok(Cw20HookMsg::CrossChainTokenBatch { orders }) => {
+            cross_chain_token_batch(deps, env, info.sender.into(), cw20_msg.sender, cw20_msg.amount, orders)
+        },
+        Ok(Cw20HookMsg::DepositRewards {}) => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            deposit_rewards_token(deps, info, sender, cw20_msg.amount)
+        },
+        Ok(Cw20HookMsg::ProvideLiquidity {}) => {
+            let token = deps.api.addr_canonicalize(info.sender.as_str())?;
+            if read_support_token_config(deps.storage, &token)?.is_none() {
+                return Err(ContractError::NotSupportToken {});
+            }
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            provide_liquidity(deps, env, sender, info.sender.to_string(), cw20_msg.amount)
+        },
+        Ok(Cw20HookMsg::TopUpLiquidity {}) => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            top_up_liquidity_token(deps, env, info, sender, cw20_msg.amount)
+        },
+        Ok(Cw20HookMsg::WithdrawLiquidity {}) => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            withdraw_liquidity(deps, env, info, sender, cw20_msg.amount)
+        },
         Err(_) => Err(ContractError::InvalidCw20Msg {}),
     }
 }
 
 pub fn cross_chain_token(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     order: Order,
 ) -> Result<Response, ContractError> {
-    // 获取转账到合约的Token
-    let token = deps.api.addr_canonicalize(order.asset.as_str())?;
+    assert_not_emergency(&deps, &env)?;
 
-    // 获取配置
-    let config: Config = read_config(deps.storage)?;
-    // 获取最小跨链金额
-    let amount_min_op = read_support_token_config(deps.storage, &token)?;
+    let (response, order_id) = deposit_token_order(&mut deps, &env, order, false)?;
 
-    // 检查订单状态
-    if read_src_order_state(deps.storage, &order.order_id)?.is_some() {
-        return Err(ContractError::SrcOrderAlreadyExist {});
-    }
+    Ok(response.set_data(to_binary(&CrossChainResponse { order_ids: vec![order_id] })?))
+}
 
-    // 币种检查，不支持的币种拒绝接收
-    if amount_min_op.is_none() {
-        return Err(ContractError::NotSupportToken {});
+// 用户预先授权额度，由合约通过TransferFrom转入Token并创建跨链订单
+pub fn cross_chain_token_from(
+    mut deps: DepsMut,
+    env: Env,
+    order: Order,
+) -> Result<Response, ContractError> {
+    assert_not_emergency(&deps, &env)?;
+
+    // Token在本次执行结束后才转入合约，无法按余额检查到账数量，且转入前不发放待发放队列
+    let asset = deps.api.addr_validate(&order.asset)?;
+    let transfer_from = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: order.from.clone(),
+            recipient: env.contract.address.to_string(),
+            amount: order.amount
+        })?,
+        funds: vec![]
+    });
+
+    let (mut response, order_id) = deposit_token_order(&mut deps, &env, order, true)?;
+    // 先转入Token，再执行铸造模式下的销毁
+    response.messages.insert(0, SubMsg::new(transfer_from));
+
+    Ok(response
+        .add_attribute("funding", "transfer_from")
+        .set_data(to_binary(&CrossChainResponse { order_ids: vec![order_id] })?)
+    )
+}
+
+// relayer提交用户链下签名的订单，用户无需支付gas，Token从用户的授权额度中转入
+pub fn cross_chain_token_signed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    signed: SignedOrder,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    assert_relayer(&deps, &info)?;
+
+    // 签名公钥需对应订单的发送方
+    let from = deps.api.addr_canonicalize(signed.order.from.as_str())?;
+    if pubkey_to_canonical(&pubkey) != Some(from.clone()) {
+        return Err(ContractError::InvalidSignature {});
     }
-    let amount_min = amount_min_op.unwrap();
 
+    let digest = signed_order_digest(&env, &signed);
+    // 签名或公钥格式错误时同样视为签名无效
+    if deps.api.secp256k1_verify(&digest, &signature, &pubkey) != Ok(true) {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    // 每个nonce只能使用一次，防止签名重放
+    let nonce = read_nonce(deps.storage, &from)?;
+    if signed.nonce != nonce {
+        return Err(ContractError::InvalidNonce {});
+    }
+    store_nonce(deps.storage, &from, &(nonce + 1))?;
+
+    let response = cross_chain_token_from(deps, env, signed.order)?;
+
+    Ok(response.add_attribute("nonce", nonce.to_string()))
+}
+
+// 一笔cw20转账提交多个Token跨链订单，转账数量需等于订单数量之和
+pub fn cross_chain_token_batch(
+    mut deps: DepsMut,
+    env: Env,
+    asset: String,
+    from: String,
+    amount: Uint128,
+    orders: Vec<DepositOrder>,
+) -> Result<Response, ContractError> {
+    assert_not_emergency(&deps, &env)?;
+    assert_batch_size(orders.len())?;
+
+    // 检查实际转账数量与订单数量之和是否一致
+    if sum_deposit_amount(&orders)? != amount {
+        return Err(StdError::generic_err("Amount Mismatch Params").into());
+    }
+
+    // 获取当前合约底仓资产余额
+    let balance = query_token_balance(
+        &deps.querier,
+        deps.api.addr_validate(&asset)?,
+        env.contract.address.clone()
+    )?;
+
+    // 合约余额需覆盖本次到账数量及不属于底仓的资金，否则实际到账数量与转账数量不一致
+    let reserved = read_reserved_balance(deps.storage, &asset)?;
+    let balance_before = match balance.checked_sub(amount) {
+        Ok(balance_before) if balance_before >= reserved => balance_before,
+        _ => return Err(ContractError::TokenBalanceMismatch {}),
+    };
+
+    let mut response = Response::new().add_attribute("method", "cross_chain_token_batch");
+    let mut order_ids: Vec<Uint128> = vec![];
+    for deposit in orders {
+        let order = deposit.into_order(asset.clone(), from.clone());
+        let (order_response, order_id) = deposit_token_order(&mut deps, &env, order, true)?;
+        response = merge_batch_response(response, order_response);
+        order_ids.push(order_id);
+    }
+
+    // 新到账的资产优先发放待发放队列
+    let (drain_messages, delivered) = drain_delivery_queue(&mut deps, &env, &asset, DEFAULT_DRAIN_LIMIT)?;
+
+    Ok(response
+        .add_attribute("asset", &asset)
+        .add_attribute("from", &from)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("order_ids", order_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))
+        .add_attribute("balance_before", balance_before.to_string())
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(drain_messages)
+        .set_data(to_binary(&CrossChainResponse { order_ids })?)
+    )
+}
+
+// 记录Token源链订单，deferred为true时由调用方统一检查到账数量及发放待发放队列
+fn deposit_token_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    order: Order,
+    deferred: bool,
+) -> Result<(Response, Uint128), ContractError> {
+    // 获取转账到合约的Token
+    let token = deps.api.addr_canonicalize(order.asset.as_str())?;
+
+    // 获取最小跨链金额
+    let amount_min_op = read_support_token_config(deps.storage, &token)?;
+
+    // 分配订单id，客户端传入的订单id仅作为同一发送方的幂等键
+    let sender = deps.api.addr_canonicalize(order.from.as_str())?;
+    let order_id = assign_order_id(deps.storage, &sender, order.client_order_id)?;
+
+    // 币种检查，不支持的币种拒绝接收
+    let amount_min = match amount_min_op {
+        Some(amount_min) => amount_min,
+        None => return Err(ContractError::NotSupportToken {}),
+    };
+
+    // 转账扣费或余额自动变化的Token无法按转账数量记账，拒绝接收
+    if read_token_capability(deps.storage, &token)? != TokenCapability::Standard {
+        return Err(ContractError::NonStandardToken {});
+    }
 
     // 检查是否满足最小额
     if order.amount < amount_min {
         return Err(ContractError::LessThenAmountMin {});
     }
 
-    // 检查手续费是否超过最大值
-    if order.cross_chain_fee > order.amount.checked_mul(MAX_CROSS_FEE).unwrap().checked_div(DENOMINATOR).unwrap() {
+    // 目标链需已登记对应的资产
+    let remote_asset = match read_route(deps.storage, &order.asset, order.dst_chain_id)? {
+        Some(remote_asset) => remote_asset,
+        None => return Err(ContractError::RouteNotExist {}),
+    };
+
+    // 接收方地址需符合目标链的地址格式
+    assert_dst_address(deps, &order)?;
+
+    // 检查手续费支付币种
+    assert_fee_denom(deps, &order)?;
+
+    // 检查手续费是否超过用户分级及渠道商对应的最大值
+    let now = env.block.time.seconds();
+    let (_, fee_rate) = read_fee_rate(deps.storage, &sender, &order.asset, &order.channel, now)?;
+    if order.cross_chain_fee > order.amount.multiply_ratio(fee_rate, DENOMINATOR) {
         return Err(ContractError::ExceedMaxCrossChainFee {});
     }
 
@@ -631,54 +1396,115 @@ pub fn cross_chain_token(
         return Err(ContractError::ExceedDeadline {});
     }
 
-    // 获取当前合约底仓资产余额
-    let balance = query_token_balance(
-        &deps.querier,
-        deps.api.addr_humanize(&token)?,
-        env.contract.address
-    )?;
+    // 批量跨链时由调用方按订单数量之和检查到账数量
+    let mut balance = Uint128::zero();
+    let mut balance_before = Uint128::zero();
+    if !deferred {
+        // 获取当前合约底仓资产余额
+        balance = query_token_balance(
+            &deps.querier,
+            deps.api.addr_humanize(&token)?,
+            env.contract.address.clone()
+        )?;
+
+        // 合约余额需覆盖本次到账数量及不属于底仓的资金，否则实际到账数量与转账数量不一致
+        let reserved = read_reserved_balance(deps.storage, &order.asset)?;
+        balance_before = match balance.checked_sub(order.amount) {
+            Ok(balance_before) if balance_before >= reserved => balance_before,
+            _ => return Err(ContractError::TokenBalanceMismatch {}),
+        };
+    }
 
     // 计算收取的费用
-    let fee = order.gas_fee.checked_add(order.cross_chain_fee).unwrap();
-
-    let balance_before = balance.checked_sub(order.amount).unwrap();
-    let balance_after = balance.checked_sub(fee).unwrap();
+    let fee = order.gas_fee.checked_add(order.cross_chain_fee)?;
 
     // 设置订单状态为已成功
-    store_src_order_state(deps.storage, &order.order_id, &Uint128::from(1u128))?;
+    store_src_order_state(deps.storage, &order_id, &Uint128::from(1u128))?;
+    // 按与EVM合约一致的编码计算订单哈希
+    let hash = Binary::from(order_hash(order_id, &order));
+    store_src_order_hash(deps.storage, &order_id, &hash)?;
+
+    // 累计手续费，指定手续费币种时从用户预存的手续费余额中扣除
+    let (fee_asset, fee_paid) = match &order.fee_denom {
+        Some(fee_denom) => {
+            let price = query_fee_price_rate(deps.as_ref(), &order.asset, fee_denom)?;
+            let fee_paid = fee * price;
+            charge_fee_balance(deps.storage, &sender, fee_denom, fee_paid)?;
+            (fee_denom.clone(), fee_paid)
+        }
+        None => (order.asset.clone(), fee),
+    };
+    // LP按比例分得跨链手续费，计入流动性
+    let lp_fee = if fee_asset == order.asset {
+        share_lp_fee(deps.storage, &order.asset, order.cross_chain_fee)?
+    } else {
+        Uint128::zero()
+    };
+    let accrued_fee = read_accrued_fee(deps.storage, &fee_asset)?.checked_add(fee_paid.checked_sub(lp_fee)?)?;
+    store_accrued_fee(deps.storage, &fee_asset, &accrued_fee)?;
 
-    // 转账给treasury
-    let mut messages: Vec<CosmosMsg> = vec![];
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: deps.api.addr_humanize(&token)?.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: deps.api.addr_humanize(&config.treasury)?.to_string(),
-            amount: fee
-        })?,
-        funds: vec![]
-    }));
+    // 按目标链精度换算跨出数量
+    let bridged = if fee_asset == order.asset { order.amount.checked_sub(fee_paid)? } else { order.amount };
+    let (remote_amount, dust) = convert_src_amount(deps.storage, &order.asset, order.dst_chain_id, bridged)?;
 
-    Ok(Response::new()
+    // 铸造模式下销毁跨出的数量，留存在合约中的手续费及余数不销毁
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut burned = Uint128::zero();
+    if read_asset_mode(deps.storage, &token)? == AssetMode::MintBurn {
+        burned = bridged.checked_sub(dust)?;
+        messages.push(burn_wrapped(deps.storage, &token, &order.asset, burned)?);
+    }
+    store_src_order(deps.storage, &order_id, &SrcOrder {
+        asset: order.asset.clone(),
+        from: sender.clone(),
+        amount: order.amount,
+        gas_fee: order.gas_fee,
+        deadline: order.deadline,
+        burned
+    })?;
+
+    // 记录用户跨链量
+    record_sender_volume(deps.storage, &sender, &order.asset, order.amount, now)?;
+
+    let mut response = Response::new()
         .add_attribute("method", "cross_chain_token")
-        .add_attribute("order_id", &order.order_id.to_string())
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("client_order_id", order.client_order_id.map(|id| id.to_string()).unwrap_or_default())
+        .add_attribute("order_hash", to_hex(hash.as_slice()))
         .add_attribute("asset", &order.asset)
         .add_attribute("from", &order.from)
         .add_attribute("to", &order.to)
         .add_attribute("amount", &order.amount.to_string())
         .add_attribute("fee", &fee.to_string())
+        .add_attribute("fee_denom", &fee_asset)
+        .add_attribute("fee_paid", fee_paid.to_string())
+        .add_attribute("lp_fee", lp_fee.to_string())
         .add_attribute("rewards", &order.rewards.to_string())
         .add_attribute("src_chain_id", &order.src_chain_id.to_string())
         .add_attribute("dst_chain_id", &order.dst_chain_id.to_string())
         .add_attribute("channel", &order.channel)
-        .add_attribute("balance_before", &balance_before.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
-    )
+        .add_attribute("remote_asset", &remote_asset)
+        .add_attribute("remote_amount", remote_amount.to_string())
+        .add_attribute("dust", dust.to_string())
+        .add_attribute("burned", burned.to_string())
+        .add_messages(messages);
+
+    if !deferred {
+        // 手续费留存在合约中，由treasury批量划转；新到账的资产优先发放待发放队列
+        let (drain_messages, delivered) = drain_delivery_queue(deps, env, &order.asset, DEFAULT_DRAIN_LIMIT)?;
+        response = response
+            .add_attribute("balance_before", &balance_before.to_string())
+            .add_attribute("balance_after", &balance.checked_sub(burned)?.to_string())
+            .add_attribute("queue_delivered", delivered.join(","))
+            .add_submessages(drain_messages);
+    }
+
+    Ok((response, order_id))
 }
 
 
 pub fn refund_token(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     order_id: Uint128,
@@ -689,125 +1515,205 @@ pub fn refund_token(
 ) -> Result<Response, ContractError> {
     assert_relayer(&deps, &info)?;
 
-    // 获取转账到合约的Token
-    let token = deps.api.addr_canonicalize(asset.as_str())?;
-    let refund_to = deps.api.addr_canonicalize(from.as_str())?;
-
-    if read_src_order_state(deps.storage, &order_id)?.is_none() {
-        return Err(ContractError::SrcOrderNotExist {});
-    }
+    let refund = RefundOrder { order_id, asset, from, amount, gas_fee };
+    assert_refund_order(&deps, &refund)?;
 
-    // 检查订单状态
-    if read_src_order_state(deps.storage, &order_id)?.unwrap() != Uint128::from(1u128) {
-        return Err(ContractError::SrcOrderNotSuccess {});
-    }
+    refund_token_order(&mut deps, &env, refund, None)
+}
 
-    // 获取当前合约底仓资产余额
-    let balance = query_token_balance(
-        &deps.querier,
-        deps.api.addr_humanize(&token)?,
-        env.contract.address
-    )?;
+// 退还Token源链订单，批量退款时从合约余额转出的部分汇总到batch中统一发放
+fn refund_token_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    refund: RefundOrder,
+    batch: Option<&mut BatchContext>,
+) -> Result<Response, ContractError> {
+    // 获取转账到合约的Token
+    let token = deps.api.addr_canonicalize(refund.asset.as_str())?;
+    let refund_to = deps.api.addr_canonicalize(refund.from.as_str())?;
 
     // 发送交易收取的gas费用
-    let amount_sub_gas = amount.checked_sub(gas_fee).unwrap();
-
-    let balance_after = balance.checked_sub(amount_sub_gas).unwrap();
+    let amount_sub_gas = refund.amount.checked_sub(refund.gas_fee)?;
 
     // 设置订单状态为已退款
-    store_src_order_state(deps.storage, &order_id, &Uint128::from(2u128))?;
+    store_src_order_state(deps.storage, &refund.order_id, &Uint128::from(2u128))?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: deps.api.addr_humanize(&token)?.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: deps.api.addr_humanize(&refund_to)?.to_string(),
-            amount: amount_sub_gas
-        })?,
-        funds: vec![]
-    }));
+    // 从合约余额转出的退款数量，铸造模式下扣除重新铸造的部分
+    let mut refund_amount = amount_sub_gas;
+    if read_asset_mode(deps.storage, &token)? == AssetMode::MintBurn {
+        let (message, remaining) = refund_burned(deps.storage, &refund.order_id, &token, &refund.asset, &deps.api.addr_humanize(&refund_to)?, amount_sub_gas)?;
+        messages.extend(message);
+        refund_amount = remaining;
+    }
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("method", "refund_token")
-        .add_attribute("order_id", &order_id.to_string())
-        .add_attribute("asset", &asset)
-        .add_attribute("from", &from)
-        .add_attribute("amount", &amount.to_string())
-        .add_attribute("gas_fee", &gas_fee.to_string())
-        .add_attribute("balance_before", &balance.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
-    )
+        .add_attribute("order_id", &refund.order_id.to_string())
+        .add_attribute("asset", &refund.asset)
+        .add_attribute("from", &refund.from)
+        .add_attribute("amount", &refund.amount.to_string())
+        .add_attribute("gas_fee", &refund.gas_fee.to_string());
+
+    match batch {
+        Some(batch) => add_batch_transfer(batch, Delivery {
+            order_id: refund.order_id,
+            leg: DeliveryLeg::Refund,
+            asset: refund.asset.clone(),
+            to: refund_to,
+            amount: refund_amount,
+            payload: None,
+            swap: None
+        })?,
+        None => {
+            // 获取当前合约底仓资产余额
+            let balance = query_token_balance(
+                &deps.querier,
+                deps.api.addr_humanize(&token)?,
+                env.contract.address.clone()
+            )?;
+
+            let balance_after = balance.checked_sub(refund_amount)?;
+            if !refund_amount.is_zero() {
+                let (message, _) = build_transfer_msg(deps.as_ref(), &refund.asset, &deps.api.addr_humanize(&refund_to)?, refund_amount)?;
+                messages.push(message);
+            }
+            response = response
+                .add_attribute("balance_before", &balance.to_string())
+                .add_attribute("balance_after", &balance_after.to_string());
+        }
+    }
+
+    Ok(response.add_messages(messages))
 }
 
 pub fn cross_chain_token_confirm(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    order_id: Uint128,
-    asset: String,
-    to: String,
-    amount: Uint128,
-    rewards: Uint128
+    mut order: ConfirmOrder,
 ) -> Result<Response, ContractError> {
     assert_relayer(&deps, &info)?;
+    assert_confirm_order(&deps, &mut order)?;
 
-    // 获取配置
-    let config: Config = read_config(deps.storage)?;
+    confirm_token_order(&mut deps, &env, order, None)
+}
 
+// 发放Token目标链订单，批量确认时直接转账的部分汇总到batch中统一发放
+fn confirm_token_order(
+    deps: &mut DepsMut,
+    env: &Env,
+    order: ConfirmOrder,
+    batch: Option<&mut BatchContext>,
+) -> Result<Response, ContractError> {
     // 获取转账到合约的Token
-    let token = deps.api.addr_canonicalize(asset.as_str())?;
-    let confirm_to = deps.api.addr_canonicalize(to.as_str())?;
+    let token = deps.api.addr_canonicalize(order.asset.as_str())?;
+    let confirm_to = deps.api.addr_canonicalize(order.to.as_str())?;
 
-    // 检查订单状态
-    if read_dst_order_state(deps.storage, &order_id)?.is_some() {
-        return Err(ContractError::DstOrderAlreadyExist {});
+    // 设置订单状态为已完成
+    record_dst_order(deps.storage, env, &order, &confirm_to)?;
+
+    // 锁定释放模式下底仓不足或已有待发放订单时进入待发放队列
+    let asset_mode = read_asset_mode(deps.storage, &token)?;
+    let committed = batch.as_ref().map(|batch| batch.committed(&order.asset)).unwrap_or_default();
+    if asset_mode == AssetMode::LockRelease
+        && should_queue_delivery(deps.as_ref(), env, &order.asset, order.amount.checked_add(committed)?)? {
+        return queue_delivery(deps, env, "cross_chain_token_confirm", batch.is_none(), PendingDelivery {
+            order_id: order.order_id,
+            asset: order.asset,
+            to: confirm_to,
+            amount: order.amount,
+            rewards: order.rewards,
+            delivery_mode: order.delivery_mode,
+            payload: order.payload,
+            swap: order.swap
+        });
     }
 
-    // 获取当前合约底仓资产余额
-    let balance = query_token_balance(
-        &deps.querier,
-        deps.api.addr_humanize(&token)?,
-        env.contract.address
-    )?;
-    let balance_after = balance.checked_sub(amount).unwrap();
+    let batched = batch.is_some();
 
-    // 设置订单状态为已完成
-    store_dst_order_state(deps.storage, &order_id, &Uint128::from(env.block.time.nanos()))?;
+    // 铸造模式下先向合约铸造本金，再按发放方式发放
+    let mut mint_messages: Vec<CosmosMsg> = vec![];
+    let mut minted = Uint128::zero();
+    if asset_mode == AssetMode::MintBurn {
+        minted = order.amount;
+        mint_messages.push(mint_wrapped(deps.storage, &token, &order.asset, &env.contract.address, minted)?);
+    }
 
-    let mut messages: Vec<CosmosMsg> = vec![];
-    // 转账给接收方
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: deps.api.addr_humanize(&token)?.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: deps.api.addr_humanize(&confirm_to)?.to_string(),
-            amount
-        })?,
-        funds: vec![]
-    }));
-    // 是否有返佣
-    if !rewards.is_zero() {
-        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&token)?.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                owner: deps.api.addr_humanize(&config.treasury)?.to_string(),
-                recipient: deps.api.addr_humanize(&confirm_to)?.to_string(),
-                amount: rewards
-            })?,
-            funds: vec![]
-        }));
+    // 从返佣资金池支付返佣
+    let rewards_paid = pay_rewards_from_pool(deps.storage, &order.order_id, &order.asset, &confirm_to, order.rewards)?;
+    let rewards_unpaid = order.rewards.checked_sub(rewards_paid)?;
+    let transfer_amount = order.amount.checked_add(rewards_paid)?;
+
+    let mut messages: Vec<SubMsg> = vec![];
+    match order.delivery_mode {
+        // 记入接收方的待领取余额
+        DeliveryMode::Credit => credit_claimable(deps.storage, &confirm_to, &order.asset, transfer_amount)?,
+        // 批量确认时本金与返佣汇总后直接转账
+        DeliveryMode::Push => match batch {
+            Some(batch) if order.payload.is_none() && order.swap.is_none() => {
+                add_order_batch_transfers(batch, Delivery {
+                    order_id: order.order_id,
+                    leg: DeliveryLeg::Principal,
+                    asset: order.asset.clone(),
+                    to: confirm_to.clone(),
+                    amount: order.amount,
+                    payload: None,
+                    swap: None
+                }, rewards_paid)?;
+                // 铸造模式下本金由本次铸造支付，不占用底仓
+                batch.commit(&order.asset, transfer_amount.checked_sub(minted)?)?;
+            },
+            // 本金与返佣分别转账给接收方
+            batch => {
+                let (order_messages, _) = build_order_submsgs(deps, env, Delivery {
+                    order_id: order.order_id,
+                    leg: DeliveryLeg::Principal,
+                    asset: order.asset.clone(),
+                    to: confirm_to.clone(),
+                    amount: order.amount,
+                    payload: order.payload.clone(),
+                    swap: order.swap.clone()
+                }, rewards_paid)?;
+                messages = order_messages;
+                if let Some(batch) = batch {
+                    batch.commit(&order.asset, transfer_amount.checked_sub(minted)?)?;
+                }
+            }
+        }
     }
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("method", "cross_chain_token_confirm")
-        .add_attribute("order_id", &order_id.to_string())
-        .add_attribute("asset", &asset)
-        .add_attribute("to", &to)
-        .add_attribute("amount", &amount.to_string())
-        .add_attribute("rewards", &rewards.to_string())
-        .add_attribute("balance_before", &balance.to_string())
-        .add_attribute("balance_after", &balance_after.to_string())
-        .add_messages(messages)
+        .add_attribute("order_id", &order.order_id.to_string())
+        .add_attribute("order_hash", order.order_hash.as_ref().map(|hash| to_hex(hash.as_slice())).unwrap_or_default())
+        .add_attribute("src_tx_hash", order.src_tx_hash.clone().unwrap_or_default())
+        .add_attribute("asset", &order.asset)
+        .add_attribute("to", &order.to)
+        .add_attribute("amount", &order.amount.to_string())
+        .add_attribute("rewards", &order.rewards.to_string())
+        .add_attribute("rewards_paid", rewards_paid.to_string())
+        .add_attribute("rewards_unpaid", rewards_unpaid.to_string())
+        .add_attribute("delivery_mode", format!("{:?}", order.delivery_mode))
+        .add_attribute("minted", minted.to_string());
+
+    // 批量确认时不逐笔查询底仓余额
+    if !batched {
+        // 获取当前合约底仓资产余额
+        let balance = query_token_balance(
+            &deps.querier,
+            deps.api.addr_humanize(&token)?,
+            env.contract.address.clone()
+        )?;
+        let balance_after = balance.checked_add(minted)?.checked_sub(transfer_amount)?;
+        response = response
+            .add_attribute("balance_before", &balance.to_string())
+            .add_attribute("balance_after", &balance_after.to_string());
+    }
+
+    Ok(response
+        .add_messages(mint_messages)
+        .add_submessages(messages)
     )
 }
 
@@ -831,12 +1737,13 @@ pub fn withdrawal_token(
         env.contract.address
     )?;
 
-    // 检查提款是否大于底仓余额
-    if amount > balance {
+    // 检查提款是否大于底仓余额，未划转的手续费、返佣资金池及LP提供的流动性不可提取
+    let reserved = read_reserved_balance(deps.storage, &asset)?.checked_add(read_lp_liquidity(deps.storage, &asset)?)?;
+    if amount > balance.saturating_sub(reserved) {
         return Err(ContractError::NotEnoughBalance {});
     }
 
-    let balance_after = balance.checked_sub(amount).unwrap();
+    let balance_after = balance.checked_sub(amount)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -879,12 +1786,13 @@ pub fn withdrawal_punish_token(
         env.contract.address
     )?;
 
-    // 检查提款是否大于底仓余额
-    if amount > balance {
+    // 检查提款是否大于底仓余额，未划转的手续费、返佣资金池及LP提供的流动性不可提取
+    let reserved = read_reserved_balance(deps.storage, &asset)?.checked_add(read_lp_liquidity(deps.storage, &asset)?)?;
+    if amount > balance.saturating_sub(reserved) {
         return Err(ContractError::NotEnoughBalance {});
     }
 
-    let balance_after = balance.checked_sub(amount).unwrap();
+    let balance_after = balance.checked_sub(amount)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -906,80 +1814,2314 @@ pub fn withdrawal_punish_token(
     )
 }
 
-/// Query
+// 将累计的手续费批量划转给treasury
+pub fn sweep_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    assets: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        // 获取配置
-        QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        // 获取支持的Token及最小兑换额
-        QueryMsg::QuerySupportToken { asset } => to_binary(&query_support_token(deps, asset)?),
-        // 获取支持的Coin及最小兑换额
-        QueryMsg::QuerySupportCoin { asset } => to_binary(&query_support_coin(deps, asset)?),
-        // 获取源链订单信息
-        QueryMsg::QuerySrcOrderStatus { order_id } => to_binary(&query_src_order_status(deps, order_id)?),
-        // 获取目标链订单信息
-        QueryMsg::QueryDstOrderStatus { order_id } => to_binary(&query_dst_order_status(deps, order_id)?)
+    // 检查是否是treasury或governor
+    let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender != config.treasury && sender != config.governor {
+        return Err(ContractError::UnauthorizedTreasury {});
     }
-}
 
-pub fn query_support_token(deps: Deps, asset: String) -> StdResult<QuerySupportTokenResponse> {
-    let token = deps.api.addr_canonicalize(asset.as_str())?;
+    let treasury = deps.api.addr_humanize(&config.treasury)?;
 
-    // 读取支持的币种最小兑换额
-    let amount_min;
-    if read_support_token_config(deps.storage, &token)?.is_some() {
-        amount_min = read_support_token_config(deps.storage, &token)?.unwrap();
-    } else {
-        amount_min = Uint128::zero();
-    }
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut swept: Vec<String> = vec![];
+    for asset in assets {
+        // 精度换算留存的余数与手续费一并划转
+        let fee = read_accrued_fee(deps.storage, &asset)?.checked_add(read_retained_dust(deps.storage, &asset)?)?;
+        if fee.is_zero() {
+            continue;
+        }
 
-    Ok(QuerySupportTokenResponse {
-        amount_min
-    })
-}
+        store_accrued_fee(deps.storage, &asset, &Uint128::zero())?;
+        store_retained_dust(deps.storage, &asset, &Uint128::zero())?;
+        let (message, tax) = build_transfer_msg(deps.as_ref(), &asset, &treasury, fee)?;
+        messages.push(message);
 
-pub fn query_support_coin(deps: Deps, asset: String) -> StdResult<QuerySupportCoinResponse> {
-    // 读取支持的币种最小兑换额
-    let amount_min;
-    if read_support_coin_config(deps.storage, &asset)?.is_some() {
-        amount_min = read_support_coin_config(deps.storage, &asset)?.unwrap();
-    } else {
-        amount_min = Uint128::zero();
+        swept.push(format!("{}:{}:{}", asset, fee, tax));
     }
 
-    Ok(QuerySupportCoinResponse {
-        amount_min
-    })
+    Ok(Response::new()
+        .add_attribute("method", "sweep_fees")
+        .add_attribute("treasury", treasury.as_str())
+        .add_attribute("fees", swept.join(","))
+        .add_messages(messages)
+    )
 }
 
-pub fn query_src_order_status(deps: Deps, order_id: Uint128) -> StdResult<QueryOrderStatusResponse> {
-    let status;
-    if read_src_order_state(deps.storage, &order_id)?.is_some() {
-        status = read_src_order_state(deps.storage, &order_id)?.unwrap();
-    } else {
-        status = Uint128::zero();
+// 设置按近30天跨链量分级的手续费率
+pub fn set_fee_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    tiers: Vec<FeeTier>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
     }
 
-    Ok(QueryOrderStatusResponse {
-        status
-    })
-}
+    // 跨链量下限必须严格递增，费率不超过最大值
+    for (i, tier) in tiers.iter().enumerate() {
+        if tier.fee_rate > MAX_CROSS_FEE {
+            return Err(ContractError::ExceedMaxFeeRate {});
+        }
+        if i > 0 && tier.volume_min <= tiers[i - 1].volume_min {
+            return Err(ContractError::InvalidFeeTiers {});
+        }
+    }
 
-pub fn query_dst_order_status(deps: Deps, order_id: Uint128) -> StdResult<QueryOrderStatusResponse> {
-    let status;
-    if read_dst_order_state(deps.storage, &order_id)?.is_some() {
+    store_fee_tiers(deps.storage, &asset, &tiers)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_fee_tiers")
+        .add_attribute("asset", &asset)
+        .add_attribute("tiers", tiers.len().to_string())
+    )
+}
+
+// 设置渠道商的手续费率
+pub fn set_channel_fee_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel: String,
+    fee_rate: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match fee_rate {
+        Some(fee_rate) => {
+            if fee_rate > MAX_CROSS_FEE {
+                return Err(ContractError::ExceedMaxFeeRate {});
+            }
+            store_channel_fee_rate(deps.storage, &channel, &fee_rate)?;
+        }
+        None => remove_channel_fee_rate(deps.storage, &channel),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_channel_fee_rate")
+        .add_attribute("channel", &channel)
+        .add_attribute("fee_rate", fee_rate.map(|r| r.to_string()).unwrap_or_default())
+    )
+}
+
+// 设置Token的转账特性
+pub fn set_token_capability(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    capability: TokenCapability,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = deps.api.addr_canonicalize(asset.as_str())?;
+    store_token_capability(deps.storage, &token, &capability)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_token_capability")
+        .add_attribute("asset", &asset)
+        .add_attribute("capability", format!("{:?}", capability))
+    )
+}
+
+// 设置Token的跨链方式及铸造上限，铸造模式要求合约为Token的minter
+pub fn set_asset_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: String,
+    mode: AssetMode,
+    supply_cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = deps.api.addr_canonicalize(asset.as_str())?;
+    if read_support_token_config(deps.storage, &token)?.is_none() {
+        return Err(ContractError::NotSupportToken {});
+    }
+
+    // 切换跨链方式前需无流通的铸造量及LP流动性，否则已铸造的Token或LP存入的资产无法按原方式退出
+    let mut supply = read_wrapped_supply(deps.storage, &token)?;
+    if read_asset_mode(deps.storage, &token)? != mode
+        && (!supply.outstanding().is_zero() || !read_lp_liquidity(deps.storage, &asset)?.is_zero()) {
+        return Err(ContractError::AssetModeInUse {});
+    }
+
+    if mode == AssetMode::MintBurn {
+        let minter: MinterResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: asset.clone(),
+            msg: to_binary(&Cw20QueryMsg::Minter {})?,
+        }))?;
+        if minter.minter != env.contract.address.as_str() {
+            return Err(ContractError::InvalidWrappedToken {});
+        }
+    }
+
+    // 铸造上限仅适用于铸造模式，且不能低于当前流通量
+    let supply_cap = if mode == AssetMode::MintBurn { supply_cap } else { None };
+    if let Some(cap) = supply_cap {
+        if cap < supply.outstanding() {
+            return Err(ContractError::ExceedSupplyCap {});
+        }
+    }
+    store_asset_mode(deps.storage, &token, &mode)?;
+    supply.supply_cap = supply_cap;
+    store_wrapped_supply(deps.storage, &token, &supply)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_asset_mode")
+        .add_attribute("asset", &asset)
+        .add_attribute("mode", format!("{:?}", mode))
+        .add_attribute("supply_cap", supply_cap.map(|cap| cap.to_string()).unwrap_or_default())
+    )
+}
+
+// 铸造Token给接收方并累计铸造数量，超过铸造上限时拒绝
+fn mint_wrapped(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    asset: &str,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<CosmosMsg, ContractError> {
+    let mut supply = read_wrapped_supply(storage, token)?;
+    supply.total_minted = supply.total_minted.checked_add(amount)?;
+    if let Some(supply_cap) = supply.supply_cap {
+        if supply.outstanding() > supply_cap {
+            return Err(ContractError::ExceedSupplyCap {});
+        }
+    }
+    store_wrapped_supply(storage, token, &supply)?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount
+        })?,
+        funds: vec![]
+    }))
+}
+
+// 销毁合约持有的Token并累计销毁数量
+fn burn_wrapped(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    asset: &str,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let mut supply = read_wrapped_supply(storage, token)?;
+    supply.total_burned = supply.total_burned.checked_add(amount)?;
+    store_wrapped_supply(storage, token, &supply)?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![]
+    }))
+}
+
+// 退款时撤销跨出时的销毁，重新铸造给接收方，不受铸造上限限制
+fn unburn_wrapped(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    asset: &str,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let mut supply = read_wrapped_supply(storage, token)?;
+    supply.total_burned = supply.total_burned.checked_sub(amount)?;
+    store_wrapped_supply(storage, token, &supply)?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: asset.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount
+        })?,
+        funds: vec![]
+    }))
+}
+
+// 铸造模式退款，按源链订单销毁的数量重新铸造，返回铸造消息及仍需从合约余额转出的数量，
+// 即留存在合约中的手续费及余数部分
+fn refund_burned(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    token: &CanonicalAddr,
+    asset: &str,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<(Option<CosmosMsg>, Uint128)> {
+    let burned = read_src_order(storage, order_id)?.map(|order| order.burned).unwrap_or_default();
+    let reminted = std::cmp::min(burned, amount);
+    if reminted.is_zero() {
+        return Ok((None, amount));
+    }
+
+    let message = unburn_wrapped(storage, token, asset, recipient, reminted)?;
+    Ok((Some(message), amount.checked_sub(reminted)?))
+}
+
+// 设置资产在其他链上的精度
+pub fn set_chain_decimals(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    chain_id: u64,
+    decimals: Option<ChainDecimals>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &decimals {
+        Some(decimals) => store_chain_decimals(deps.storage, &asset, chain_id, decimals)?,
+        None => remove_chain_decimals(deps.storage, &asset, chain_id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_chain_decimals")
+        .add_attribute("asset", &asset)
+        .add_attribute("chain_id", chain_id.to_string())
+        .add_attribute("decimals", decimals.map(|d| format!("{}:{}", d.local_decimals, d.remote_decimals)).unwrap_or_default())
+    )
+}
+
+// 设置资产在目标链上对应的资产
+pub fn set_route(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    chain_id: u64,
+    remote_asset: Option<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &remote_asset {
+        Some(remote_asset) => store_route(deps.storage, &asset, chain_id, remote_asset)?,
+        None => remove_route(deps.storage, &asset, chain_id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_route")
+        .add_attribute("asset", &asset)
+        .add_attribute("chain_id", chain_id.to_string())
+        .add_attribute("remote_asset", remote_asset.unwrap_or_default())
+    )
+}
+
+// 设置用户申请退款的等待时间及relayer答复的时间窗口
+pub fn set_refund_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    timeout: u64,
+    response_window: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    store_refund_config(deps.storage, &RefundConfig {
+        timeout,
+        response_window
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_refund_config")
+        .add_attribute("timeout", timeout.to_string())
+        .add_attribute("response_window", response_window.to_string())
+    )
+}
+
+// 源链订单超过截止时间及等待时间后，发送方申请退款
+pub fn request_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: Uint128,
+) -> Result<Response, ContractError> {
+    let order = match read_src_order(deps.storage, &order_id)? {
+        Some(order) => order,
+        None => return Err(ContractError::SrcOrderNotExist {}),
+    };
+
+    // 只有发送方可以申请
+    if deps.api.addr_canonicalize(info.sender.as_str())? != order.from {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // 检查订单状态
+    if read_src_order_state(deps.storage, &order_id)? != Some(Uint128::from(1u128)) {
+        return Err(ContractError::SrcOrderNotSuccess {});
+    }
+
+    if read_refund_request(deps.storage, &order_id)?.is_some() {
+        return Err(ContractError::RefundRequestAlreadyExist {});
+    }
+
+    let refund_config = query_refund_config(deps.as_ref())?;
+    let now = env.block.time.seconds();
+    if now <= order.deadline.saturating_add(refund_config.timeout) {
+        return Err(ContractError::RefundNotAvailable {});
+    }
+
+    store_refund_request(deps.storage, &order_id, &RefundRequest {
+        status: RefundRequestStatus::Pending,
+        requested_at: now,
+        proof: None
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "request_refund")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("respond_before", now.saturating_add(refund_config.response_window).to_string())
+    )
+}
+
+// relayer或risk_control答复退款申请，未送达时立即退款，已送达时在时间窗口内附上凭证拒绝
+pub fn respond_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: Uint128,
+    delivered: bool,
+    proof: Option<String>,
+) -> Result<Response, ContractError> {
+    if assert_relayer(&deps, &info).is_err() {
+        assert_risk_control(&deps, &info)?;
+    }
+
+    let mut request = match read_refund_request(deps.storage, &order_id)? {
+        Some(request) if request.status == RefundRequestStatus::Pending => request,
+        _ => return Err(ContractError::RefundRequestNotPending {}),
+    };
+
+    if !delivered {
+        return refund_src_order(deps, "respond_refund", order_id, Some(request));
+    }
+
+    let refund_config = query_refund_config(deps.as_ref())?;
+    if env.block.time.seconds() > request.requested_at.saturating_add(refund_config.response_window) {
+        return Err(ContractError::RefundWindowPassed {});
+    }
+
+    request.status = RefundRequestStatus::Rejected;
+    request.proof = proof;
+    store_refund_request(deps.storage, &order_id, &request)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "respond_refund")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("status", "rejected")
+        .add_attribute("proof", request.proof.unwrap_or_default())
+    )
+}
+
+// 答复时间窗口结束后仍未答复的退款申请，任何人可执行退款
+pub fn execute_refund(
+    deps: DepsMut,
+    env: Env,
+    order_id: Uint128,
+) -> Result<Response, ContractError> {
+    let request = match read_refund_request(deps.storage, &order_id)? {
+        Some(request) if request.status == RefundRequestStatus::Pending => request,
+        _ => return Err(ContractError::RefundRequestNotPending {}),
+    };
+
+    let refund_config = query_refund_config(deps.as_ref())?;
+    if env.block.time.seconds() <= request.requested_at.saturating_add(refund_config.response_window) {
+        return Err(ContractError::RefundWindowNotPassed {});
+    }
+
+    refund_src_order(deps, "execute_refund", order_id, Some(request))
+}
+
+// 设置relayer心跳的超时时间
+pub fn set_heartbeat_timeout(
+    deps: DepsMut,
+    info: MessageInfo,
+    timeout: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    store_heartbeat_timeout(deps.storage, &timeout)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_heartbeat_timeout")
+        .add_attribute("timeout", timeout.to_string())
+    )
+}
+
+// relayer心跳，心跳恢复后自动退出紧急模式
+pub fn heartbeat(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    checkpoint: Option<Checkpoint>,
+    delivered: Vec<Uint128>,
+    processed: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    assert_relayer(&deps, &info)?;
+
+    let now = env.block.time.seconds();
+    store_last_heartbeat(deps.storage, &now)?;
+
+    let mut response = Response::new()
+        .add_attribute("method", "heartbeat")
+        .add_attribute("time", now.to_string());
+    if let Some(checkpoint) = checkpoint {
+        store_checkpoint(deps.storage, checkpoint.chain_id, &ChainCheckpoint {
+            height: checkpoint.height,
+            updated_at: now
+        })?;
+        response = response
+            .add_attribute("chain_id", checkpoint.chain_id.to_string())
+            .add_attribute("height", checkpoint.height.to_string());
+    }
+
+    // 标记已在目标链送达的源链订单，此后不可再退款或紧急取回
+    let mut marked: Vec<String> = vec![];
+    for order_id in delivered {
+        if read_src_order_state(deps.storage, &order_id)? == Some(Uint128::from(1u128)) {
+            store_src_order_state(deps.storage, &order_id, &Uint128::from(3u128))?;
+            marked.push(order_id.to_string());
+        }
+    }
+    if !marked.is_empty() {
+        response = response.add_attribute("delivered", marked.join(","));
+    }
+
+    // 已处理到的源链订单号只增不减，需在送达标记之后更新，之前未标记送达的订单方可紧急取回
+    if let Some(processed) = processed {
+        if read_processed_order(deps.storage)?.is_none_or(|current| processed > current) {
+            store_processed_order(deps.storage, &processed)?;
+            response = response.add_attribute("processed", processed.to_string());
+        }
+    }
+
+    Ok(response)
+}
+
+// 紧急模式下发送方取回未完成的源链订单资产
+pub fn emergency_reclaim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: Uint128,
+) -> Result<Response, ContractError> {
+    if !is_emergency(deps.as_ref(), &env)? {
+        return Err(ContractError::NotEmergencyMode {});
+    }
+
+    let order = match read_src_order(deps.storage, &order_id)? {
+        Some(order) => order,
+        None => return Err(ContractError::SrcOrderNotExist {}),
+    };
+
+    // 只有发送方可以取回
+    if deps.api.addr_canonicalize(info.sender.as_str())? != order.from {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // 截止时间后relayer仍持续心跳超过一个超时时间，目标链不会再确认该订单且relayer有足够时间标记送达，
+    // 截止时间更晚的订单可能已在relayer停止心跳前送达，需等待relayer恢复
+    let last_heartbeat = read_last_heartbeat(deps.storage)?.unwrap_or_default();
+    let timeout = read_heartbeat_timeout(deps.storage)?.unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT);
+    if order.deadline.saturating_add(timeout) > last_heartbeat {
+        return Err(ContractError::ReclaimNotAvailable {});
+    }
+
+    // 只有relayer确认已处理且未上报送达的订单可取回，未处理到的订单可能已在目标链送达
+    match read_processed_order(deps.storage)? {
+        Some(processed) if order_id <= processed => {},
+        _ => return Err(ContractError::ReclaimNotAvailable {}),
+    }
+
+    let request = read_refund_request(deps.storage, &order_id)?;
+    refund_src_order(deps, "emergency_reclaim", order_id, request)
+}
+
+// relayer曾发送过心跳且最近一次心跳已超时，则处于紧急模式
+fn is_emergency(deps: Deps, env: &Env) -> StdResult<bool> {
+    let last_heartbeat = match read_last_heartbeat(deps.storage)? {
+        Some(last_heartbeat) => last_heartbeat,
+        None => return Ok(false),
+    };
+    let timeout = read_heartbeat_timeout(deps.storage)?.unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT);
+
+    Ok(env.block.time.seconds() > last_heartbeat.saturating_add(timeout))
+}
+
+// 按源链订单记录退还扣除gas费用后的资产给发送方
+fn refund_src_order(
+    mut deps: DepsMut,
+    method: &str,
+    order_id: Uint128,
+    request: Option<RefundRequest>,
+) -> Result<Response, ContractError> {
+    let order = match read_src_order(deps.storage, &order_id)? {
+        Some(order) => order,
+        None => return Err(ContractError::SrcOrderNotExist {}),
+    };
+
+    // relayer可能已通过RefundCoin/RefundToken退款
+    if read_src_order_state(deps.storage, &order_id)? != Some(Uint128::from(1u128)) {
+        return Err(ContractError::SrcOrderNotSuccess {});
+    }
+
+    // 设置订单状态为已退款
+    store_src_order_state(deps.storage, &order_id, &Uint128::from(2u128))?;
+    if let Some(mut request) = request {
+        request.status = RefundRequestStatus::Refunded;
+        store_refund_request(deps.storage, &order_id, &request)?;
+    }
+
+    let amount_sub_gas = order.amount.checked_sub(order.gas_fee)?;
+    let refund_to = deps.api.addr_humanize(&order.from)?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut refund_amount = amount_sub_gas;
+    if read_support_coin_config(deps.storage, &order.asset)?.is_none() {
+        let token = deps.api.addr_canonicalize(order.asset.as_str())?;
+        if read_asset_mode(deps.storage, &token)? == AssetMode::MintBurn {
+            let (message, remaining) = refund_burned(deps.branch().storage, &order_id, &token, &order.asset, &refund_to, amount_sub_gas)?;
+            messages.extend(message);
+            refund_amount = remaining;
+        }
+    }
+    let mut tax = Uint128::zero();
+    if !refund_amount.is_zero() {
+        let (message, transfer_tax) = build_transfer_msg(deps.as_ref(), &order.asset, &refund_to, refund_amount)?;
+        messages.push(message);
+        tax = transfer_tax;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", method)
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("status", "refunded")
+        .add_attribute("asset", &order.asset)
+        .add_attribute("from", refund_to.as_str())
+        .add_attribute("amount", order.amount.to_string())
+        .add_attribute("gas_fee", order.gas_fee.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_messages(messages)
+    )
+}
+
+// 设置目标链的地址格式
+pub fn set_chain_address_format(
+    deps: DepsMut,
+    info: MessageInfo,
+    chain_id: u64,
+    format: Option<AddressFormat>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &format {
+        Some(format) => store_chain_address_format(deps.storage, chain_id, format)?,
+        None => remove_chain_address_format(deps.storage, chain_id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_chain_address_format")
+        .add_attribute("chain_id", chain_id.to_string())
+        .add_attribute("format", format.map(|f| format!("{:?}", f)).unwrap_or_default())
+    )
+}
+
+// 记录目标链订单的确认时间及来源链信息，用于追溯来源链交易及重复确认检查
+fn record_dst_order(
+    storage: &mut dyn Storage,
+    env: &Env,
+    order: &ConfirmOrder,
+    to: &CanonicalAddr,
+) -> StdResult<()> {
+    store_dst_order_state(storage, &order.order_id, &Uint128::from(env.block.time.nanos()))?;
+    if let Some(order_hash) = &order.order_hash {
+        store_dst_order_hash(storage, order_hash, &order.order_id)?;
+    }
+    if let (Some(src_chain_id), Some(src_tx_hash)) = (order.src_chain_id, &order.src_tx_hash) {
+        store_dst_src_tx(storage, src_chain_id, src_tx_hash, order.src_event_index, &order.order_id)?;
+    }
+
+    store_dst_order(storage, &order.order_id, &DstOrder {
+        asset: order.asset.clone(),
+        to: to.clone(),
+        amount: order.amount,
+        rewards: order.rewards,
+        src_chain_id: order.src_chain_id,
+        src_tx_hash: order.src_tx_hash.clone(),
+        src_event_index: order.src_event_index,
+        src_sender: order.src_sender.clone(),
+        // 来源链精度下的原始数量，未传入时与本链数量相同
+        src_amount: order.remote_amount.unwrap_or_else(|| Uint256::from(order.amount)),
+        order_hash: order.order_hash.clone()
+    })
+}
+
+// 按目标链精度换算跨出数量，返回目标链数量及留存的余数
+fn convert_src_amount(
+    storage: &mut dyn Storage,
+    asset: &String,
+    dst_chain_id: u64,
+    amount: Uint128,
+) -> Result<(Uint256, Uint128), ContractError> {
+    let decimals = match read_chain_decimals(storage, asset, dst_chain_id)? {
+        Some(decimals) => decimals,
+        None => return Ok((Uint256::from(amount), Uint128::zero())),
+    };
+
+    let (remote_amount, dust) = to_remote_amount(amount, &decimals)?;
+    if !dust.is_zero() {
+        if decimals.dust_rule == DustRule::Reject {
+            return Err(ContractError::DustNotAllowed {});
+        }
+        let retained = read_retained_dust(storage, asset)?.checked_add(dust)?;
+        store_retained_dust(storage, asset, &retained)?;
+    }
+
+    Ok((remote_amount, dust))
+}
+
+// 按来源链精度换算目标链订单的本链数量，relayer同时传入的数量需与换算结果一致
+fn resolve_confirm_amount(storage: &dyn Storage, order: &mut ConfirmOrder) -> Result<(), ContractError> {
+    let remote_amount = match order.remote_amount {
+        Some(remote_amount) => remote_amount,
+        None => return Ok(()),
+    };
+    let src_chain_id = match order.src_chain_id {
+        Some(src_chain_id) => src_chain_id,
+        None => return Err(ContractError::InvalidRemoteAmount {}),
+    };
+
+    let amount = match read_chain_decimals(storage, &order.asset, src_chain_id)? {
+        Some(decimals) => {
+            // 来源链的余数无法在本链发放
+            let (amount, dust) = to_local_amount(remote_amount, &decimals)?;
+            if !dust.is_zero() && decimals.dust_rule == DustRule::Reject {
+                return Err(ContractError::DustNotAllowed {});
+            }
+            amount
+        }
+        None => to_uint128(remote_amount)?,
+    };
+    if !order.amount.is_zero() && order.amount != amount {
+        return Err(ContractError::InvalidRemoteAmount {});
+    }
+    order.amount = amount;
+
+    Ok(())
+}
+
+// custodian补充Coin底仓并发放待发放队列
+pub fn top_up_liquidity_coin(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_custodian(&deps, &info)?;
+
+    let mut messages: Vec<SubMsg> = vec![];
+    let mut delivered: Vec<String> = vec![];
+    for coin in info.funds.iter() {
+        // 币种检查，不支持的币种拒绝接收
+        if read_support_coin_config(deps.storage, &coin.denom)?.is_none() {
+            return Err(ContractError::NotSupportToken {});
+        }
+
+        let (drain_messages, drain_delivered) = drain_delivery_queue(&mut deps, &env, &coin.denom, DEFAULT_DRAIN_LIMIT)?;
+        messages.extend(drain_messages);
+        delivered.extend(drain_delivered);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "top_up_liquidity")
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(messages)
+    )
+}
+
+// custodian补充Token底仓并发放待发放队列
+pub fn top_up_liquidity_token(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是custodian
+    if deps.api.addr_canonicalize(sender.as_str())? != config.custodian {
+        return Err(ContractError::UnauthorizedCustodian {});
+    }
+
+    // 币种检查，不支持的币种拒绝接收
+    let token = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if read_support_token_config(deps.storage, &token)?.is_none() {
+        return Err(ContractError::NotSupportToken {});
+    }
+
+    let asset = info.sender.to_string();
+    let (messages, delivered) = drain_delivery_queue(&mut deps, &env, &asset, DEFAULT_DRAIN_LIMIT)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "top_up_liquidity")
+        .add_attribute("asset", &asset)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(messages)
+    )
+}
+
+// 使用当前底仓发放待发放队列中的订单，任何人可调用
+pub fn process_queue(
+    mut deps: DepsMut,
+    env: Env,
+    asset: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = std::cmp::min(limit.unwrap_or(DEFAULT_DRAIN_LIMIT), MAX_DRAIN_LIMIT);
+    let (messages, delivered) = drain_delivery_queue(&mut deps, &env, &asset, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "process_queue")
+        .add_attribute("asset", &asset)
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(messages)
+    )
+}
+
+// 底仓不足或已有待发放订单时，目标链订单需进入待发放队列
+fn should_queue_delivery(deps: Deps, env: &Env, asset: &String, amount: Uint128) -> StdResult<bool> {
+    if !read_delivery_queue(deps.storage, asset)?.is_empty() {
+        return Ok(true);
+    }
+
+    Ok(amount > query_available_liquidity(deps, &env.contract.address, asset)?)
+}
+
+// 目标链订单加入待发放队列，并尝试按顺序发放
+fn queue_delivery(
+    deps: &mut DepsMut,
+    env: &Env,
+    method: &str,
+    drain: bool,
+    delivery: PendingDelivery,
+) -> Result<Response, ContractError> {
+    let mut queue = read_delivery_queue(deps.storage, &delivery.asset)?;
+    let index = queue.tail;
+    store_pending_delivery(deps.storage, &delivery.asset, index, &delivery)?;
+    queue.tail += 1;
+    queue.total_pending = queue.total_pending.checked_add(delivery.amount)?;
+    store_delivery_queue(deps.storage, &delivery.asset, &queue)?;
+
+    // 批量确认时本批汇总的转账尚未执行，底仓余额不准确，只入队不发放
+    let (messages, delivered) = if drain {
+        drain_delivery_queue(deps, env, &delivery.asset, DEFAULT_DRAIN_LIMIT)?
+    } else {
+        (vec![], vec![])
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", method)
+        .add_attribute("order_id", delivery.order_id.to_string())
+        .add_attribute("asset", &delivery.asset)
+        .add_attribute("to", deps.api.addr_humanize(&delivery.to)?.as_str())
+        .add_attribute("amount", delivery.amount.to_string())
+        .add_attribute("rewards", delivery.rewards.to_string())
+        .add_attribute("queue_index", index.to_string())
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_submessages(messages)
+    )
+}
+
+// 按先后顺序发放待发放队列，底仓不足以发放队首订单时停止，返回转账消息及已发放的订单id
+fn drain_delivery_queue(
+    deps: &mut DepsMut,
+    env: &Env,
+    asset: &String,
+    limit: u32,
+) -> Result<(Vec<SubMsg>, Vec<String>), ContractError> {
+    let mut queue = read_delivery_queue(deps.storage, asset)?;
+    if queue.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+
+    let mut available = query_available_liquidity(deps.as_ref(), &env.contract.address, asset)?;
+    let mut messages: Vec<SubMsg> = vec![];
+    let mut delivered: Vec<String> = vec![];
+    while !queue.is_empty() && (delivered.len() as u32) < limit {
+        let delivery = match read_pending_delivery(deps.storage, asset, queue.head)? {
+            Some(delivery) => delivery,
+            None => return Err(StdError::not_found("PendingDelivery").into()),
+        };
+        if delivery.amount > available {
+            break;
+        }
+
+        remove_pending_delivery(deps.storage, asset, queue.head);
+        queue.head += 1;
+        queue.total_pending = queue.total_pending.checked_sub(delivery.amount)?;
+        available = available.checked_sub(delivery.amount)?;
+
+        // 从返佣资金池支付返佣，与本金一起发放给接收方
+        let rewards_paid = pay_rewards_from_pool(deps.storage, &delivery.order_id, asset, &delivery.to, delivery.rewards)?;
+        let transfer_amount = delivery.amount.checked_add(rewards_paid)?;
+        match delivery.delivery_mode {
+            DeliveryMode::Credit => credit_claimable(deps.storage, &delivery.to, asset, transfer_amount)?,
+            DeliveryMode::Push => {
+                let (order_messages, _) = build_order_submsgs(deps, env, Delivery {
+                    order_id: delivery.order_id,
+                    leg: DeliveryLeg::Principal,
+                    asset: asset.clone(),
+                    to: delivery.to,
+                    amount: delivery.amount,
+                    payload: delivery.payload,
+                    swap: delivery.swap
+                }, rewards_paid)?;
+                messages.extend(order_messages);
+            }
+        }
+        delivered.push(delivery.order_id.to_string());
+    }
+    store_delivery_queue(deps.storage, asset, &queue)?;
+
+    Ok((messages, delivered))
+}
+
+// 领取待领取余额
+pub fn claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    assets: Vec<String>,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut claimed: Vec<String> = vec![];
+    for asset in assets {
+        let amount = read_claimable(deps.storage, &owner, &asset)?;
+        if amount.is_zero() {
+            continue;
+        }
+
+        store_claimable(deps.storage, &owner, &asset, &Uint128::zero())?;
+        let total = read_claimable_total(deps.storage, &asset)?.checked_sub(amount)?;
+        store_claimable_total(deps.storage, &asset, &total)?;
+
+        let (message, tax) = build_transfer_msg(deps.as_ref(), &asset, &info.sender, amount)?;
+        messages.push(message);
+        claimed.push(format!("{}:{}:{}", asset, amount, tax));
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "claim")
+        .add_attribute("owner", info.sender.as_str())
+        .add_attribute("claimed", claimed.join(","))
+        .add_messages(messages)
+    )
+}
+
+// 增加接收方的待领取余额
+fn credit_claimable(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    asset: &String,
+    amount: Uint128,
+) -> StdResult<()> {
+    let claimable = read_claimable(storage, owner, asset)?.checked_add(amount)?;
+    store_claimable(storage, owner, asset, &claimable)?;
+
+    let total = read_claimable_total(storage, asset)?.checked_add(amount)?;
+    store_claimable_total(storage, asset, &total)
+}
+
+// 登记资产的LP Token
+pub fn register_lp_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: String,
+    lp_token: String,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if read_lp_pool(deps.storage, &asset)?.is_some() {
+        return Err(ContractError::LpPoolAlreadyExist {});
+    }
+
+    // LP Token必须由本合约铸造
+    let minter: MinterResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: lp_token.clone(),
+        msg: to_binary(&Cw20QueryMsg::Minter {})?,
+    }))?;
+    if minter.minter != env.contract.address.as_str() {
+        return Err(ContractError::InvalidLpToken {});
+    }
+
+    // LP Token不能有初始发行量，否则份额与流动性不对应
+    let token_info: TokenInfoResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: lp_token.clone(),
+        msg: to_binary(&Cw20QueryMsg::TokenInfo {})?,
+    }))?;
+    if !token_info.total_supply.is_zero() {
+        return Err(ContractError::InvalidLpToken {});
+    }
+
+    let lp_token_raw = deps.api.addr_canonicalize(lp_token.as_str())?;
+    store_lp_pool(deps.storage, &asset, &LpPool {
+        lp_token: lp_token_raw.clone(),
+        total_share: Uint128::zero(),
+        liquidity: Uint128::zero(),
+        fee_share: Uint128::zero(),
+        permissionless: false
+    })?;
+    store_lp_token_asset(deps.storage, &lp_token_raw, &asset)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "register_lp_token")
+        .add_attribute("asset", &asset)
+        .add_attribute("lp_token", &lp_token)
+    )
+}
+
+// 设置LP分得跨链手续费的比例及是否允许任何人提供流动性
+pub fn set_lp_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    fee_share: Uint128,
+    permissionless: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if fee_share > DENOMINATOR {
+        return Err(ContractError::ExceedMaxFeeRate {});
+    }
+
+    let mut pool = match read_lp_pool(deps.storage, &asset)? {
+        Some(pool) => pool,
+        None => return Err(ContractError::LpPoolNotExist {}),
+    };
+    pool.fee_share = fee_share;
+    pool.permissionless = permissionless;
+    store_lp_pool(deps.storage, &asset, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_lp_config")
+        .add_attribute("asset", &asset)
+        .add_attribute("fee_share", fee_share.to_string())
+        .add_attribute("permissionless", permissionless.to_string())
+    )
+}
+
+// 设置LP白名单
+pub fn set_lp_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    provider: String,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let provider_raw = deps.api.addr_canonicalize(provider.as_str())?;
+    store_lp_allowlist(deps.storage, &asset, &provider_raw, allowed)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_lp_allowlist")
+        .add_attribute("asset", &asset)
+        .add_attribute("provider", &provider)
+        .add_attribute("allowed", allowed.to_string())
+    )
+}
+
+// 提供流动性，按当前份额价格铸造LP Token
+pub fn provide_liquidity(
+    mut deps: DepsMut,
+    env: Env,
+    provider: Addr,
+    asset: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut pool = match read_lp_pool(deps.storage, &asset)? {
+        Some(pool) => pool,
+        None => return Err(ContractError::LpPoolNotExist {}),
+    };
+
+    // 检查白名单
+    let provider_raw = deps.api.addr_canonicalize(provider.as_str())?;
+    if !pool.permissionless && !read_lp_allowlist(deps.storage, &asset, &provider_raw)? {
+        return Err(ContractError::NotInLpAllowlist {});
+    }
+
+    let share = if pool.total_share.is_zero() || pool.liquidity.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(pool.total_share, pool.liquidity)
+    };
+    if share.is_zero() {
+        return Err(ContractError::LessThenAmountMin {});
+    }
+
+    pool.total_share = pool.total_share.checked_add(share)?;
+    pool.liquidity = pool.liquidity.checked_add(amount)?;
+    store_lp_pool(deps.storage, &asset, &pool)?;
+
+    // 新增的流动性优先发放待发放队列
+    let (drain_messages, delivered) = drain_delivery_queue(&mut deps, &env, &asset, DEFAULT_DRAIN_LIMIT)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "provide_liquidity")
+        .add_attribute("asset", &asset)
+        .add_attribute("provider", provider.as_str())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("share", share.to_string())
+        .add_attribute("queue_delivered", delivered.join(","))
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&pool.lp_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: provider.to_string(),
+                amount: share
+            })?,
+            funds: vec![]
+        }))
+        .add_submessages(drain_messages)
+    )
+}
+
+// 销毁LP Token并赎回流动性，赎回数量不能超过合约中可用的资产
+pub fn withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    provider: Addr,
+    share: Uint128,
+) -> Result<Response, ContractError> {
+    let lp_token = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let asset = match read_lp_token_asset(deps.storage, &lp_token)? {
+        Some(asset) => asset,
+        None => return Err(ContractError::LpPoolNotExist {}),
+    };
+    let mut pool = match read_lp_pool(deps.storage, &asset)? {
+        Some(pool) => pool,
+        None => return Err(ContractError::LpPoolNotExist {}),
+    };
+
+    if pool.total_share.is_zero() {
+        return Err(ContractError::NotEnoughLiquidity {});
+    }
+
+    // 待发放队列优先使用底仓
+    let amount = share.multiply_ratio(pool.liquidity, pool.total_share);
+    let available = query_available_liquidity(deps.as_ref(), &env.contract.address, &asset)?
+        .saturating_sub(read_delivery_queue(deps.storage, &asset)?.total_pending);
+    if amount > available {
+        return Err(ContractError::NotEnoughLiquidity {});
+    }
+
+    pool.total_share = pool.total_share.checked_sub(share)?;
+    pool.liquidity = pool.liquidity.checked_sub(amount)?;
+    store_lp_pool(deps.storage, &asset, &pool)?;
+
+    let (message, tax) = build_transfer_msg(deps.as_ref(), &asset, &provider, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_liquidity")
+        .add_attribute("asset", &asset)
+        .add_attribute("provider", provider.as_str())
+        .add_attribute("share", share.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: info.sender.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: share
+            })?,
+            funds: vec![]
+        }))
+        .add_message(message)
+    )
+}
+
+// LP按比例分得跨链手续费，返回分得的数量
+fn share_lp_fee(
+    storage: &mut dyn Storage,
+    asset: &String,
+    cross_chain_fee: Uint128,
+) -> StdResult<Uint128> {
+    let mut pool = match read_lp_pool(storage, asset)? {
+        Some(pool) if !pool.total_share.is_zero() => pool,
+        _ => return Ok(Uint128::zero()),
+    };
+
+    let lp_fee = cross_chain_fee.multiply_ratio(pool.fee_share, DENOMINATOR);
+    pool.liquidity = pool.liquidity.checked_add(lp_fee)?;
+    store_lp_pool(storage, asset, &pool)?;
+
+    Ok(lp_fee)
+}
+
+// 设置手续费币种价格
+pub fn set_fee_price(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    denom: String,
+    price: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // 手续费币种必须是支持的Coin
+    if read_support_coin_config(deps.storage, &denom)?.is_none() {
+        return Err(ContractError::InvalidFeeDenom {});
+    }
+
+    match price {
+        Some(price) => store_fee_price(deps.storage, &asset, &denom, &price)?,
+        None => remove_fee_price(deps.storage, &asset, &denom),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_fee_price")
+        .add_attribute("asset", &asset)
+        .add_attribute("denom", &denom)
+        .add_attribute("price", price.map(|p| p.to_string()).unwrap_or_default())
+    )
+}
+
+// 设置价格预言机合约，价格表中没有配置时从预言机查询
+pub fn set_price_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let oracle_addr = match &oracle {
+        Some(oracle) => Some(deps.api.addr_canonicalize(oracle.as_str())?),
+        None => None,
+    };
+    store_price_oracle(deps.storage, &oracle_addr)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_price_oracle")
+        .add_attribute("oracle", oracle.unwrap_or_default())
+    )
+}
+
+// 预存用于支付手续费的Coin
+pub fn deposit_fee_balance(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let mut deposited: Vec<String> = vec![];
+    for coin in info.funds.iter() {
+        // 手续费币种必须是支持的Coin
+        if read_support_coin_config(deps.storage, &coin.denom)?.is_none() {
+            return Err(ContractError::InvalidFeeDenom {});
+        }
+
+        credit_fee_balance(deps.storage, &owner, &coin.denom, coin.amount)?;
+        deposited.push(format!("{}:{}", coin.denom, coin.amount));
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "deposit_fee_balance")
+        .add_attribute("owner", info.sender.as_str())
+        .add_attribute("amount", deposited.join(","))
+    )
+}
+
+// 取回预存的手续费余额
+pub fn withdraw_fee_balance(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    charge_fee_balance(deps.storage, &owner, &denom, amount)?;
+
+    // 扣除稳定费
+    let (transfer_coin, tax) = deduct_tax(&deps.querier, Coin {
+        denom: denom.clone(),
+        amount,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_fee_balance")
+        .add_attribute("owner", info.sender.as_str())
+        .add_attribute("denom", &denom)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![transfer_coin],
+        }))
+    )
+}
+
+// 增加用户预存的手续费余额
+fn credit_fee_balance(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    denom: &String,
+    amount: Uint128,
+) -> StdResult<()> {
+    let balance = read_fee_balance(storage, owner, denom)?.checked_add(amount)?;
+    store_fee_balance(storage, owner, denom, &balance)?;
+
+    let total = read_fee_balance_total(storage, denom)?.checked_add(amount)?;
+    store_fee_balance_total(storage, denom, &total)
+}
+
+// 扣除用户预存的手续费余额
+fn charge_fee_balance(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    denom: &String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let balance = read_fee_balance(storage, owner, denom)?;
+    if amount > balance {
+        return Err(ContractError::NotEnoughFeeBalance {});
+    }
+    store_fee_balance(storage, owner, denom, &balance.checked_sub(amount)?)?;
+
+    let total = read_fee_balance_total(storage, denom)?.checked_sub(amount)?;
+    store_fee_balance_total(storage, denom, &total)?;
+
+    Ok(())
+}
+
+// treasury充值返佣资金池(Coin)
+pub fn deposit_rewards_coin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_treasury(&deps, &info.sender)?;
+
+    let mut deposited: Vec<String> = vec![];
+    for coin in info.funds.iter() {
+        // 币种检查，不支持的币种拒绝接收
+        if read_support_coin_config(deps.storage, &coin.denom)?.is_none() {
+            return Err(ContractError::NotSupportToken {});
+        }
+
+        let pool = read_rewards_pool(deps.storage, &coin.denom)?.checked_add(coin.amount)?;
+        store_rewards_pool(deps.storage, &coin.denom, &pool)?;
+
+        deposited.push(format!("{}:{}", coin.denom, coin.amount));
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "deposit_rewards")
+        .add_attribute("rewards", deposited.join(","))
+    )
+}
+
+// treasury充值返佣资金池(Token)
+pub fn deposit_rewards_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_treasury(&deps, &sender)?;
+
+    // 币种检查，不支持的币种拒绝接收
+    let token = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if read_support_token_config(deps.storage, &token)?.is_none() {
+        return Err(ContractError::NotSupportToken {});
+    }
+
+    let asset = info.sender.to_string();
+    let pool = read_rewards_pool(deps.storage, &asset)?.checked_add(amount)?;
+    store_rewards_pool(deps.storage, &asset, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "deposit_rewards")
+        .add_attribute("rewards", format!("{}:{}", asset, amount))
+    )
+}
+
+// treasury取回返佣资金池中的资金
+pub fn withdraw_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_treasury(&deps, &info.sender)?;
+
+    let pool = read_rewards_pool(deps.storage, &asset)?;
+    if amount > pool {
+        return Err(ContractError::NotEnoughBalance {});
+    }
+    store_rewards_pool(deps.storage, &asset, &pool.checked_sub(amount)?)?;
+
+    let (message, tax) = build_transfer_msg(deps.as_ref(), &asset, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_rewards")
+        .add_attribute("asset", &asset)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_message(message)
+    )
+}
+
+// 资金池补足后，补发目标链订单未支付的返佣
+pub fn pay_unpaid_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: Uint128,
+) -> Result<Response, ContractError> {
+    assert_relayer(&deps, &info)?;
+
+    let unpaid = match read_unpaid_rewards(deps.storage, &order_id)? {
+        Some(unpaid) => unpaid,
+        None => return Err(ContractError::UnpaidRewardsNotExist {}),
+    };
+
+    remove_unpaid_rewards(deps.storage, &order_id);
+    let rewards_paid = pay_rewards_from_pool(deps.storage, &order_id, &unpaid.asset, &unpaid.to, unpaid.amount)?;
+    if rewards_paid.is_zero() {
+        return Err(ContractError::RewardsPoolEmpty {});
+    }
+
+    let to = deps.api.addr_humanize(&unpaid.to)?;
+    let (message, tax) = build_delivery_submsg(&mut deps, &env, Delivery {
+        order_id,
+        leg: DeliveryLeg::Rewards,
+        asset: unpaid.asset.clone(),
+        to: unpaid.to.clone(),
+        amount: rewards_paid,
+        payload: None,
+        swap: None
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "pay_unpaid_rewards")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("asset", &unpaid.asset)
+        .add_attribute("to", to.as_str())
+        .add_attribute("rewards_paid", rewards_paid.to_string())
+        .add_attribute("rewards_unpaid", unpaid.amount.checked_sub(rewards_paid)?.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_submessage(message)
+    )
+}
+
+// 登记或移除发放时可使用的交易对，登记时从交易对合约读取两种资产
+pub fn set_swap_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair_addr = deps.api.addr_validate(pair.as_str())?;
+    let pair_raw = deps.api.addr_canonicalize(pair.as_str())?;
+    let mut assets: Vec<String> = vec![];
+    if enabled {
+        let pair_info = query_pair_info(&deps.querier, &pair_addr)?;
+        let pair_assets = [pair_info.asset_infos[0].to_asset(), pair_info.asset_infos[1].to_asset()];
+        store_swap_pair(deps.storage, &pair_raw, &pair_assets)?;
+        assets = pair_assets.to_vec();
+    } else {
+        remove_swap_pair(deps.storage, &pair_raw);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_swap_pair")
+        .add_attribute("pair", &pair)
+        .add_attribute("enabled", enabled.to_string())
+        .add_attribute("assets", assets.join(","))
+    )
+}
+
+// 通过交易对兑换并发放给接收方，仅本合约可调用，兑换后检查接收方实际获得的数量
+pub fn swap_delivery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    swap: SwapRoute,
+    asset: String,
+    amount: Uint128,
+    to: String,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair = deps.api.addr_validate(swap.pair.as_str())?;
+    let to = deps.api.addr_validate(to.as_str())?;
+    let pair_info = query_pair_info(&deps.querier, &pair)?;
+    let ask_info = match pair_info.asset_infos.iter().find(|info| info.to_asset() != asset) {
+        Some(ask_info) => ask_info.clone(),
+        None => return Err(ContractError::InvalidSwapPair {}),
+    };
+    let prev_balance = query_pair_asset_balance(&deps.querier, &ask_info, to.clone())?;
+
+    let (offer_asset, tax) = build_offer_asset(deps.as_ref(), &asset, amount)?;
+    let swap_msg = if let AssetInfo::NativeToken { denom } = &offer_asset.info {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: pair.to_string(),
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: offer_asset.clone(),
+                belief_price: None,
+                max_spread: None,
+                to: Some(to.to_string())
+            })?,
+            funds: vec![Coin {
+                denom: denom.clone(),
+                amount: offer_asset.amount,
+            }],
+        })
+    } else {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: asset.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount,
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: None,
+                    to: Some(to.to_string())
+                })?
+            })?,
+            funds: vec![]
+        })
+    };
+    let assert_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+            asset_info: ask_info,
+            prev_balance,
+            minimum_receive: swap.minimum_receive,
+            receiver: to.to_string()
+        })?,
+        funds: vec![]
+    });
+
+    Ok(Response::new()
+        .add_attribute("method", "swap_delivery")
+        .add_attribute("pair", pair.as_str())
+        .add_attribute("asset", &asset)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("to", to.as_str())
+        .add_attribute("tax", tax.to_string())
+        .add_message(swap_msg)
+        .add_message(assert_msg)
+    )
+}
+
+// 检查兑换后接收方获得的数量不少于最少获得数量，仅本合约可调用
+pub fn assert_minimum_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    minimum_receive: Uint128,
+    receiver: String,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let receiver = deps.api.addr_validate(receiver.as_str())?;
+    let balance = query_pair_asset_balance(&deps.querier, &asset_info, receiver.clone())?;
+    let received = balance.saturating_sub(prev_balance);
+    if received < minimum_receive {
+        return Err(ContractError::MinimumReceiveNotMet {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "assert_minimum_receive")
+        .add_attribute("receiver", receiver.as_str())
+        .add_attribute("received", received.to_string())
+    )
+}
+
+// 设置目标链订单可调用的合约
+pub fn set_call_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是governor
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.governor {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
+    store_call_allowlist(deps.storage, &contract_raw, allowed)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_call_allowlist")
+        .add_attribute("contract", &contract)
+        .add_attribute("allowed", allowed.to_string())
+    )
+}
+
+// 重试发放失败的转账，接收方或relayer可调用
+pub fn retry_delivery(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: Uint128,
+    leg: DeliveryLeg,
+) -> Result<Response, ContractError> {
+    let delivery = match read_failed_delivery(deps.storage, &order_id, &leg)? {
+        Some(delivery) => delivery,
+        None => return Err(ContractError::FailedDeliveryNotExist {}),
+    };
+    if deps.api.addr_canonicalize(info.sender.as_str())? != delivery.to {
+        assert_relayer(&deps, &info)?;
+    }
+
+    remove_failed_delivery(deps.storage, &order_id, &leg);
+    let total = read_failed_delivery_total(deps.storage, &delivery.asset)?.checked_sub(delivery.amount)?;
+    store_failed_delivery_total(deps.storage, &delivery.asset, &total)?;
+    // 本金与返佣均已重新发放时清除失败标记，退款使用源链订单id，不涉及目标链订单
+    if leg != DeliveryLeg::Refund
+        && read_failed_delivery(deps.storage, &order_id, &DeliveryLeg::Principal)?.is_none()
+        && read_failed_delivery(deps.storage, &order_id, &DeliveryLeg::Rewards)?.is_none() {
+        remove_dst_delivery_failed(deps.storage, &order_id);
+    }
+
+    let to = deps.api.addr_humanize(&delivery.to)?;
+    let asset = delivery.asset.clone();
+    let amount = delivery.amount;
+    let (message, tax) = build_delivery_submsg(&mut deps, &env, delivery)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "retry_delivery")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("leg", leg.as_str())
+        .add_attribute("asset", &asset)
+        .add_attribute("to", to.as_str())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("tax", tax.to_string())
+        .add_submessage(message)
+    )
+}
+
+// 转账SubMsg回调，合约调用或兑换失败时改为直接转账，转账失败时记录该笔转账以便重试
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if let Some(deliveries) = read_batch_reply(deps.storage, msg.id)? {
+        remove_batch_reply(deps.storage, msg.id);
+        return batch_transfer_reply(deps, deliveries, msg.result);
+    }
+
+    let delivery = match read_delivery_reply(deps.storage, msg.id)? {
+        Some(delivery) => delivery,
+        None => return Err(StdError::not_found("Delivery").into()),
+    };
+    remove_delivery_reply(deps.storage, msg.id);
+
+    let response = Response::new()
+        .add_attribute("method", "delivery_reply")
+        .add_attribute("order_id", delivery.order_id.to_string())
+        .add_attribute("leg", delivery.leg.as_str())
+        .add_attribute("asset", &delivery.asset)
+        .add_attribute("amount", delivery.amount.to_string());
+
+    match msg.result {
+        ContractResult::Ok(_) => Ok(response.add_attribute("status", "delivered")),
+        // 合约调用或兑换失败时改为直接转账原资产给接收方，转账失败时再记录以便重试
+        ContractResult::Err(err) if delivery.payload.is_some() || delivery.swap.is_some() => {
+            let (message, tax) = build_delivery_submsg(&mut deps, &env, Delivery {
+                payload: None,
+                swap: None,
+                ..delivery
+            })?;
+
+            Ok(response
+                .add_attribute("status", "fallback")
+                .add_attribute("error", err)
+                .add_attribute("tax", tax.to_string())
+                .add_submessage(message)
+            )
+        },
+        ContractResult::Err(err) => {
+            record_failed_delivery(deps.storage, delivery)?;
+
+            Ok(response
+                .add_attribute("status", "failed")
+                .add_attribute("error", err)
+            )
+        }
+    }
+}
+
+// 批量汇总转账的SubMsg回调，转账失败时按汇总的各笔发放分别记录以便重试
+fn batch_transfer_reply(
+    deps: DepsMut,
+    deliveries: Vec<Delivery>,
+    result: ContractResult<SubMsgExecutionResponse>,
+) -> Result<Response, ContractError> {
+    let orders: Vec<String> = deliveries.iter()
+        .map(|delivery| format!("{}:{}", delivery.order_id, delivery.leg.as_str()))
+        .collect();
+    let response = Response::new()
+        .add_attribute("method", "batch_transfer_reply")
+        .add_attribute("orders", orders.join(","));
+
+    match result {
+        ContractResult::Ok(_) => Ok(response.add_attribute("status", "delivered")),
+        ContractResult::Err(err) => {
+            for delivery in deliveries {
+                record_failed_delivery(deps.storage, delivery)?;
+            }
+
+            Ok(response
+                .add_attribute("status", "failed")
+                .add_attribute("error", err)
+            )
+        }
+    }
+}
+
+// 记录发放失败的转账，转账失败的资金仍留在合约中，不计入底仓
+fn record_failed_delivery(storage: &mut dyn Storage, delivery: Delivery) -> StdResult<()> {
+    let total = read_failed_delivery_total(storage, &delivery.asset)?.checked_add(delivery.amount)?;
+    store_failed_delivery_total(storage, &delivery.asset, &total)?;
+
+    // 同一订单同一部分已有失败记录时合并数量，避免覆盖导致资金无法重试
+    let failed = match read_failed_delivery(storage, &delivery.order_id, &delivery.leg)? {
+        Some(failed) => Delivery {
+            amount: failed.amount.checked_add(delivery.amount)?,
+            ..failed
+        },
+        None => delivery,
+    };
+    store_failed_delivery(storage, &failed)?;
+    if failed.leg != DeliveryLeg::Refund {
+        store_dst_delivery_failed(storage, &failed.order_id)?;
+    }
+
+    Ok(())
+}
+
+// 本金与返佣分别以SubMsg发放给接收方，返回转账消息及稳定费
+fn build_order_submsgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    principal: Delivery,
+    rewards: Uint128,
+) -> StdResult<(Vec<SubMsg>, Uint128)> {
+    // 仅本金携带调用参数及兑换路径，返佣直接转账
+    let rewards = Delivery {
+        leg: DeliveryLeg::Rewards,
+        amount: rewards,
+        payload: None,
+        swap: None,
+        ..principal.clone()
+    };
+
+    let mut messages: Vec<SubMsg> = vec![];
+    let mut tax = Uint128::zero();
+    for delivery in [principal, rewards] {
+        if delivery.amount.is_zero() {
+            continue;
+        }
+
+        let (message, leg_tax) = build_delivery_submsg(deps, env, delivery)?;
+        messages.push(message);
+        tax = tax.checked_add(leg_tax)?;
+    }
+
+    Ok((messages, tax))
+}
+
+// 以SubMsg发出单笔转账、合约调用或兑换，并记录回调所需的发放信息
+fn build_delivery_submsg(deps: &mut DepsMut, env: &Env, mut delivery: Delivery) -> StdResult<(SubMsg, Uint128)> {
+    let to = deps.api.addr_humanize(&delivery.to)?;
+    let swap_msg = match &delivery.swap {
+        Some(swap) => build_swap_msg(deps.as_ref(), env, &delivery.asset, swap, &to, delivery.amount)?,
+        None => None,
+    };
+    let (message, tax) = match (&delivery.payload, swap_msg) {
+        (Some(payload), _) => build_call_msg(deps.as_ref(), &delivery.asset, &to, delivery.amount, payload.clone())?,
+        (None, Some(swap_msg)) => swap_msg,
+        (None, None) => {
+            // 无法满足最少获得数量时发放原资产
+            delivery.swap = None;
+            build_transfer_msg(deps.as_ref(), &delivery.asset, &to, delivery.amount)?
+        }
+    };
+
+    let id = next_reply_id(deps.storage)?;
+    store_delivery_reply(deps.storage, id, &delivery)?;
+
+    Ok((SubMsg::reply_always(message, id), tax))
+}
+
+// 从返佣资金池扣除返佣，资金池不足的部分记录为未支付，返回实际支付的数量
+fn pay_rewards_from_pool(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    asset: &String,
+    to: &CanonicalAddr,
+    rewards: Uint128,
+) -> StdResult<Uint128> {
+    if rewards.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let pool = read_rewards_pool(storage, asset)?;
+    let rewards_paid = std::cmp::min(pool, rewards);
+    store_rewards_pool(storage, asset, &pool.checked_sub(rewards_paid)?)?;
+
+    if rewards_paid < rewards {
+        store_unpaid_rewards(storage, order_id, &UnpaidRewards {
+            asset: asset.clone(),
+            to: to.clone(),
+            amount: rewards.checked_sub(rewards_paid)?
+        })?;
+    }
+
+    Ok(rewards_paid)
+}
+
+/// Query
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        // 获取配置
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        // 获取支持的Token及最小兑换额
+        QueryMsg::QuerySupportToken { asset } => to_binary(&query_support_token(deps, asset)?),
+        // 获取支持的Coin及最小兑换额
+        QueryMsg::QuerySupportCoin { asset } => to_binary(&query_support_coin(deps, asset)?),
+        // 获取源链订单信息
+        QueryMsg::QuerySrcOrderStatus { order_id } => to_binary(&query_src_order_status(deps, order_id)?),
+        // 获取目标链订单信息
+        QueryMsg::QueryDstOrderStatus { order_id } => to_binary(&query_dst_order_status(deps, order_id)?),
+        // 获取未划转的手续费
+        QueryMsg::QueryUnsweptFee { asset } => to_binary(&query_unswept_fee(deps, asset)?),
+        // 获取返佣资金池余额
+        QueryMsg::QueryRewardsPool { asset } => to_binary(&query_rewards_pool(deps, asset)?),
+        // 获取目标链订单未支付的返佣
+        QueryMsg::QueryUnpaidRewards { order_id } => to_binary(&query_unpaid_rewards(deps, order_id)?),
+        // 获取手续费分级配置
+        QueryMsg::QueryFeeTiers { asset } => to_binary(&query_fee_tiers(deps, asset)?),
+        // 获取用户的跨链量及生效的手续费率
+        QueryMsg::QueryFeeQuote { sender, asset, amount, channel } => to_binary(&query_fee_quote(deps, env, sender, asset, amount, channel)?),
+        // 获取手续费币种价格
+        QueryMsg::QueryFeePrice { asset, denom } => to_binary(&query_fee_price(deps, asset, denom)?),
+        // 获取用户预存的手续费余额
+        QueryMsg::QueryFeeBalance { address, denom } => to_binary(&query_fee_balance(deps, address, denom)?),
+        // 获取Token的转账特性
+        QueryMsg::QueryTokenCapability { asset } => to_binary(&query_token_capability(deps, asset)?),
+        // 获取LP资金池、份额价格及利用率
+        QueryMsg::QueryLpPool { asset } => to_binary(&query_lp_pool(deps, env, asset)?),
+        // 获取待发放队列及底仓缺口
+        QueryMsg::QueryPendingDeliveries { asset, start_after, limit } => to_binary(&query_pending_deliveries(deps, env, asset, start_after, limit)?),
+        // 获取接收方的待领取余额
+        QueryMsg::QueryClaimable { address, asset } => to_binary(&query_claimable(deps, address, asset)?),
+        // 获取目标链订单发放失败的转账
+        QueryMsg::QueryFailedDeliveries { order_id } => to_binary(&query_failed_deliveries(deps, order_id)?),
+        // 获取合约是否可被目标链订单调用
+        QueryMsg::QueryCallAllowlist { contract } => to_binary(&query_call_allowlist(deps, contract)?),
+        // 获取已登记交易对的两种资产
+        QueryMsg::QuerySwapPair { pair } => to_binary(&query_swap_pair(deps, pair)?),
+        // 获取铸造模式Token的供应量
+        QueryMsg::QueryWrappedSupply { asset } => to_binary(&query_wrapped_supply(deps, asset)?),
+        // 获取资产在其他链上的精度
+        QueryMsg::QueryChainDecimals { asset, chain_id } => to_binary(&query_chain_decimals(deps, asset, chain_id)?),
+        // 获取精度换算留存的余数
+        QueryMsg::QueryRetainedDust { asset } => to_binary(&query_retained_dust(deps, asset)?),
+        // 获取资产在所有目标链上对应的资产
+        QueryMsg::QueryRoutes { asset } => to_binary(&query_routes(deps, asset)?),
+        // 获取目标链的地址格式
+        QueryMsg::QueryChainAddressFormat { chain_id } => to_binary(&query_chain_address_format(deps, chain_id)?),
+        // 获取用户申请退款的等待时间及relayer答复的时间窗口
+        QueryMsg::QueryRefundConfig {} => to_binary(&query_refund_config(deps)?),
+        // 获取用户的退款申请
+        QueryMsg::QueryRefundRequest { order_id } => to_binary(&query_refund_request(deps, order_id)?),
+        // 获取relayer最近一次心跳及是否处于紧急模式
+        QueryMsg::QueryLiveness {} => to_binary(&query_liveness(deps, env)?),
+        // 获取relayer已处理到的源链区块高度
+        QueryMsg::QueryCheckpoint { chain_id } => to_binary(&query_checkpoint(deps, chain_id)?),
+        // 获取用户下一笔链下签名订单的nonce
+        QueryMsg::QueryNonce { address } => to_binary(&query_nonce(deps, address)?),
+        // 获取源链订单的订单哈希
+        QueryMsg::QueryOrderHash { order_id } => to_binary(&query_order_hash(deps, order_id)?),
+        // 获取来源链订单哈希对应的目标链订单id
+        QueryMsg::QueryDstOrderByHash { order_hash } => to_binary(&query_dst_order_by_hash(deps, order_hash)?),
+        // 获取目标链订单及来源链信息
+        QueryMsg::QueryDstOrder { order_id } => to_binary(&query_dst_order(deps, order_id)?),
+        // 获取发送方的幂等键对应的订单id
+        QueryMsg::QueryClientOrder { sender, client_order_id } => to_binary(&query_client_order(deps, sender, client_order_id)?),
+        // 获取链下签名订单需签名的摘要
+        QueryMsg::QuerySignedOrderDigest { signed } => to_binary(&query_signed_order_digest(env, signed)?)
+    }
+}
+
+pub fn query_support_token(deps: Deps, asset: String) -> StdResult<QuerySupportTokenResponse> {
+    let token = deps.api.addr_canonicalize(asset.as_str())?;
+
+    // 读取支持的币种最小兑换额
+    let amount_min;
+    if read_support_token_config(deps.storage, &token)?.is_some() {
+        amount_min = read_support_token_config(deps.storage, &token)?.unwrap();
+    } else {
+        amount_min = Uint128::zero();
+    }
+
+    Ok(QuerySupportTokenResponse {
+        amount_min
+    })
+}
+
+pub fn query_support_coin(deps: Deps, asset: String) -> StdResult<QuerySupportCoinResponse> {
+    // 读取支持的币种最小兑换额
+    let amount_min;
+    if read_support_coin_config(deps.storage, &asset)?.is_some() {
+        amount_min = read_support_coin_config(deps.storage, &asset)?.unwrap();
+    } else {
+        amount_min = Uint128::zero();
+    }
+
+    Ok(QuerySupportCoinResponse {
+        amount_min
+    })
+}
+
+pub fn query_src_order_status(deps: Deps, order_id: Uint128) -> StdResult<QueryOrderStatusResponse> {
+    let status;
+    if read_src_order_state(deps.storage, &order_id)?.is_some() {
+        status = read_src_order_state(deps.storage, &order_id)?.unwrap();
+    } else {
+        status = Uint128::zero();
+    }
+
+    Ok(QueryOrderStatusResponse {
+        status
+    })
+}
+
+pub fn query_dst_order_status(deps: Deps, order_id: Uint128) -> StdResult<QueryOrderStatusResponse> {
+    let status;
+    if read_dst_order_state(deps.storage, &order_id)?.is_some() {
         status = read_dst_order_state(deps.storage, &order_id)?.unwrap();
     } else {
         status = Uint128::zero();
     }
 
-    Ok(QueryOrderStatusResponse {
-        status
+    Ok(QueryOrderStatusResponse {
+        status
+    })
+}
+
+pub fn query_unswept_fee(deps: Deps, asset: String) -> StdResult<QueryUnsweptFeeResponse> {
+    Ok(QueryUnsweptFeeResponse {
+        amount: read_accrued_fee(deps.storage, &asset)?
+    })
+}
+
+pub fn query_rewards_pool(deps: Deps, asset: String) -> StdResult<QueryRewardsPoolResponse> {
+    Ok(QueryRewardsPoolResponse {
+        amount: read_rewards_pool(deps.storage, &asset)?
+    })
+}
+
+pub fn query_unpaid_rewards(deps: Deps, order_id: Uint128) -> StdResult<QueryUnpaidRewardsResponse> {
+    match read_unpaid_rewards(deps.storage, &order_id)? {
+        Some(unpaid) => Ok(QueryUnpaidRewardsResponse {
+            asset: unpaid.asset,
+            to: deps.api.addr_humanize(&unpaid.to)?.to_string(),
+            amount: unpaid.amount
+        }),
+        None => Ok(QueryUnpaidRewardsResponse {
+            asset: String::new(),
+            to: String::new(),
+            amount: Uint128::zero()
+        }),
+    }
+}
+
+pub fn query_fee_tiers(deps: Deps, asset: String) -> StdResult<QueryFeeTiersResponse> {
+    Ok(QueryFeeTiersResponse {
+        tiers: read_fee_tiers(deps.storage, &asset)?
+    })
+}
+
+pub fn query_fee_quote(
+    deps: Deps,
+    env: Env,
+    sender: String,
+    asset: String,
+    amount: Uint128,
+    channel: String,
+) -> StdResult<QueryFeeQuoteResponse> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    let (volume, fee_rate) = read_fee_rate(deps.storage, &sender, &asset, &channel, env.block.time.seconds())?;
+
+    Ok(QueryFeeQuoteResponse {
+        volume,
+        fee_rate,
+        max_cross_chain_fee: amount.multiply_ratio(fee_rate, DENOMINATOR)
+    })
+}
+
+pub fn query_fee_price(deps: Deps, asset: String, denom: String) -> StdResult<QueryFeePriceResponse> {
+    let price = query_fee_price_rate(deps, &asset, &denom)
+        .map_err(|_| StdError::generic_err("Fee Price Not Found"))?;
+
+    Ok(QueryFeePriceResponse {
+        price
+    })
+}
+
+pub fn query_fee_balance(deps: Deps, address: String, denom: String) -> StdResult<QueryFeeBalanceResponse> {
+    let owner = deps.api.addr_canonicalize(address.as_str())?;
+
+    Ok(QueryFeeBalanceResponse {
+        amount: read_fee_balance(deps.storage, &owner, &denom)?
+    })
+}
+
+pub fn query_token_capability(deps: Deps, asset: String) -> StdResult<QueryTokenCapabilityResponse> {
+    let token = deps.api.addr_canonicalize(asset.as_str())?;
+
+    Ok(QueryTokenCapabilityResponse {
+        capability: read_token_capability(deps.storage, &token)?
+    })
+}
+
+pub fn query_lp_pool(deps: Deps, env: Env, asset: String) -> StdResult<QueryLpPoolResponse> {
+    let pool = match read_lp_pool(deps.storage, &asset)? {
+        Some(pool) => pool,
+        None => return Err(StdError::generic_err("Lp Pool Not Exist")),
+    };
+
+    let available = query_available_liquidity(deps, &env.contract.address, &asset)?;
+
+    let share_price = if pool.total_share.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(pool.liquidity, pool.total_share)
+    };
+    let utilisation = if pool.liquidity.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(pool.liquidity.saturating_sub(available), pool.liquidity)
+    };
+
+    Ok(QueryLpPoolResponse {
+        lp_token: deps.api.addr_humanize(&pool.lp_token)?,
+        total_share: pool.total_share,
+        liquidity: pool.liquidity,
+        fee_share: pool.fee_share,
+        permissionless: pool.permissionless,
+        share_price,
+        available,
+        utilisation
+    })
+}
+
+pub fn query_pending_deliveries(
+    deps: Deps,
+    env: Env,
+    asset: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<QueryPendingDeliveriesResponse> {
+    let queue = read_delivery_queue(deps.storage, &asset)?;
+    let available = query_available_liquidity(deps, &env.contract.address, &asset)?;
+
+    let start = match start_after {
+        Some(start_after) => std::cmp::max(start_after + 1, queue.head),
+        None => queue.head,
+    };
+    let limit = std::cmp::min(limit.unwrap_or(DEFAULT_DRAIN_LIMIT), MAX_DRAIN_LIMIT) as u64;
+    let end = std::cmp::min(queue.tail, start.saturating_add(limit));
+
+    let mut deliveries: Vec<PendingDeliveryResponse> = vec![];
+    for index in start..end {
+        if let Some(delivery) = read_pending_delivery(deps.storage, &asset, index)? {
+            deliveries.push(PendingDeliveryResponse {
+                index,
+                order_id: delivery.order_id,
+                to: deps.api.addr_humanize(&delivery.to)?,
+                amount: delivery.amount,
+                rewards: delivery.rewards,
+                delivery_mode: delivery.delivery_mode,
+                payload: delivery.payload,
+                swap: delivery.swap
+            });
+        }
+    }
+
+    Ok(QueryPendingDeliveriesResponse {
+        deliveries,
+        total_pending: queue.total_pending,
+        available,
+        shortfall: queue.total_pending.saturating_sub(available)
+    })
+}
+
+pub fn query_claimable(deps: Deps, address: String, asset: String) -> StdResult<QueryClaimableResponse> {
+    let owner = deps.api.addr_canonicalize(address.as_str())?;
+
+    Ok(QueryClaimableResponse {
+        amount: read_claimable(deps.storage, &owner, &asset)?
+    })
+}
+
+pub fn query_failed_deliveries(deps: Deps, order_id: Uint128) -> StdResult<QueryFailedDeliveriesResponse> {
+    let mut deliveries: Vec<FailedDeliveryResponse> = vec![];
+    for leg in [DeliveryLeg::Principal, DeliveryLeg::Rewards, DeliveryLeg::Refund] {
+        if let Some(delivery) = read_failed_delivery(deps.storage, &order_id, &leg)? {
+            deliveries.push(FailedDeliveryResponse {
+                leg,
+                asset: delivery.asset,
+                to: deps.api.addr_humanize(&delivery.to)?,
+                amount: delivery.amount
+            });
+        }
+    }
+
+    Ok(QueryFailedDeliveriesResponse { deliveries })
+}
+
+pub fn query_call_allowlist(deps: Deps, contract: String) -> StdResult<QueryCallAllowlistResponse> {
+    let contract_raw = deps.api.addr_canonicalize(contract.as_str())?;
+
+    Ok(QueryCallAllowlistResponse {
+        allowed: read_call_allowlist(deps.storage, &contract_raw)?
+    })
+}
+
+pub fn query_swap_pair(deps: Deps, pair: String) -> StdResult<QuerySwapPairResponse> {
+    let pair_raw = deps.api.addr_canonicalize(pair.as_str())?;
+
+    Ok(QuerySwapPairResponse {
+        assets: read_swap_pair(deps.storage, &pair_raw)?.map(|assets| assets.to_vec()).unwrap_or_default()
+    })
+}
+
+pub fn query_wrapped_supply(deps: Deps, asset: String) -> StdResult<QueryWrappedSupplyResponse> {
+    let token = deps.api.addr_canonicalize(asset.as_str())?;
+    let supply = read_wrapped_supply(deps.storage, &token)?;
+
+    Ok(QueryWrappedSupplyResponse {
+        mode: read_asset_mode(deps.storage, &token)?,
+        supply_cap: supply.supply_cap,
+        total_minted: supply.total_minted,
+        total_burned: supply.total_burned,
+        outstanding: supply.outstanding()
+    })
+}
+
+pub fn query_chain_decimals(deps: Deps, asset: String, chain_id: u64) -> StdResult<QueryChainDecimalsResponse> {
+    Ok(QueryChainDecimalsResponse {
+        decimals: read_chain_decimals(deps.storage, &asset, chain_id)?
+    })
+}
+
+pub fn query_retained_dust(deps: Deps, asset: String) -> StdResult<QueryRetainedDustResponse> {
+    Ok(QueryRetainedDustResponse {
+        amount: read_retained_dust(deps.storage, &asset)?
+    })
+}
+
+pub fn query_routes(deps: Deps, asset: String) -> StdResult<QueryRoutesResponse> {
+    let routes = read_routes(deps.storage, &asset)?
+        .into_iter()
+        .map(|(chain_id, remote_asset)| RouteResponse { chain_id, remote_asset })
+        .collect();
+
+    Ok(QueryRoutesResponse { routes })
+}
+
+pub fn query_chain_address_format(deps: Deps, chain_id: u64) -> StdResult<QueryChainAddressFormatResponse> {
+    Ok(QueryChainAddressFormatResponse {
+        format: read_chain_address_format(deps.storage, chain_id)?
+    })
+}
+
+pub fn query_refund_config(deps: Deps) -> StdResult<QueryRefundConfigResponse> {
+    Ok(match read_refund_config(deps.storage)? {
+        Some(config) => QueryRefundConfigResponse {
+            timeout: config.timeout,
+            response_window: config.response_window
+        },
+        None => QueryRefundConfigResponse {
+            timeout: DEFAULT_REFUND_TIMEOUT,
+            response_window: DEFAULT_REFUND_RESPONSE_WINDOW
+        },
+    })
+}
+
+pub fn query_refund_request(deps: Deps, order_id: Uint128) -> StdResult<QueryRefundRequestResponse> {
+    let request = match read_refund_request(deps.storage, &order_id)? {
+        Some(request) => request,
+        None => return Err(StdError::not_found("RefundRequest")),
+    };
+    let refund_config = query_refund_config(deps)?;
+
+    Ok(QueryRefundRequestResponse {
+        status: request.status,
+        requested_at: request.requested_at,
+        respond_before: request.requested_at.saturating_add(refund_config.response_window),
+        proof: request.proof
+    })
+}
+
+pub fn query_liveness(deps: Deps, env: Env) -> StdResult<QueryLivenessResponse> {
+    Ok(QueryLivenessResponse {
+        last_heartbeat: read_last_heartbeat(deps.storage)?,
+        timeout: read_heartbeat_timeout(deps.storage)?.unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT),
+        emergency: is_emergency(deps, &env)?,
+        processed_order: read_processed_order(deps.storage)?
+    })
+}
+
+pub fn query_checkpoint(deps: Deps, chain_id: u64) -> StdResult<QueryCheckpointResponse> {
+    let checkpoint = read_checkpoint(deps.storage, chain_id)?;
+
+    Ok(QueryCheckpointResponse {
+        height: checkpoint.as_ref().map(|c| c.height),
+        updated_at: checkpoint.map(|c| c.updated_at)
+    })
+}
+
+pub fn query_nonce(deps: Deps, address: String) -> StdResult<QueryNonceResponse> {
+    let owner = deps.api.addr_canonicalize(address.as_str())?;
+
+    Ok(QueryNonceResponse { nonce: read_nonce(deps.storage, &owner)? })
+}
+
+pub fn query_order_hash(deps: Deps, order_id: Uint128) -> StdResult<QueryOrderHashResponse> {
+    Ok(QueryOrderHashResponse { order_hash: read_src_order_hash(deps.storage, &order_id)? })
+}
+
+pub fn query_dst_order_by_hash(deps: Deps, order_hash: Binary) -> StdResult<QueryDstOrderByHashResponse> {
+    Ok(QueryDstOrderByHashResponse { order_id: read_dst_order_hash(deps.storage, &order_hash)? })
+}
+
+pub fn query_dst_order(deps: Deps, order_id: Uint128) -> StdResult<QueryDstOrderResponse> {
+    let order = match read_dst_order(deps.storage, &order_id)? {
+        Some(order) => Some(DstOrderResponse {
+            asset: order.asset,
+            to: deps.api.addr_humanize(&order.to)?,
+            amount: order.amount,
+            rewards: order.rewards,
+            src_chain_id: order.src_chain_id,
+            src_tx_hash: order.src_tx_hash,
+            src_event_index: order.src_event_index,
+            src_sender: order.src_sender,
+            src_amount: order.src_amount,
+            order_hash: order.order_hash
+        }),
+        None => None,
+    };
+
+    Ok(QueryDstOrderResponse {
+        confirmed_at: read_dst_order_state(deps.storage, &order_id)?,
+        delivery_failed: read_dst_delivery_failed(deps.storage, &order_id)?,
+        order
     })
 }
 
+pub fn query_client_order(deps: Deps, sender: String, client_order_id: Uint128) -> StdResult<QueryClientOrderResponse> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+
+    Ok(QueryClientOrderResponse { order_id: read_client_order(deps.storage, &sender, &client_order_id)? })
+}
+
+pub fn query_signed_order_digest(env: Env, signed: SignedOrder) -> StdResult<QuerySignedOrderDigestResponse> {
+    Ok(QuerySignedOrderDigestResponse { digest: Binary::from(signed_order_digest(&env, &signed)) })
+}
+
 pub fn query_config(deps: Deps) -> StdResult<QueryConfigResponse> {
     // 获取配置
     let config: Config = read_config(deps.storage)?;
@@ -1023,6 +4165,220 @@ pub fn query_token_balance(
     Ok(res.balance)
 }
 
+// 获取用户近30天的跨链量及生效的最大跨链手续费率
+pub fn read_fee_rate(
+    storage: &dyn Storage,
+    sender: &CanonicalAddr,
+    asset: &String,
+    channel: &String,
+    now: u64,
+) -> StdResult<(Uint128, Uint128)> {
+    let first_day = (now / SECONDS_PER_DAY).saturating_sub(VOLUME_WINDOW_DAYS - 1);
+    let volume = read_sender_volume(storage, sender, asset)?
+        .iter()
+        .filter(|v| v.day >= first_day)
+        .fold(Uint128::zero(), |acc, v| acc.saturating_add(v.amount));
+
+    // 取满足跨链量下限的最高分级，未配置分级时使用默认最大值
+    let mut fee_rate = read_fee_tiers(storage, asset)?
+        .iter()
+        .rev()
+        .find(|tier| volume >= tier.volume_min)
+        .map(|tier| tier.fee_rate)
+        .unwrap_or(MAX_CROSS_FEE);
+
+    // 渠道商费率更低时使用渠道商费率
+    if let Some(channel_fee_rate) = read_channel_fee_rate(storage, channel)? {
+        fee_rate = std::cmp::min(fee_rate, channel_fee_rate);
+    }
+
+    Ok((volume, fee_rate))
+}
+
+// 按天累计用户跨链量，只保留统计窗口内的记录
+fn record_sender_volume(
+    storage: &mut dyn Storage,
+    sender: &CanonicalAddr,
+    asset: &String,
+    amount: Uint128,
+    now: u64,
+) -> StdResult<()> {
+    let today = now / SECONDS_PER_DAY;
+    let first_day = today.saturating_sub(VOLUME_WINDOW_DAYS - 1);
+
+    let mut volumes: Vec<DailyVolume> = read_sender_volume(storage, sender, asset)?
+        .into_iter()
+        .filter(|v| v.day >= first_day)
+        .collect();
+
+    match volumes.iter_mut().find(|v| v.day == today) {
+        Some(v) => v.amount = v.amount.checked_add(amount)?,
+        None => volumes.push(DailyVolume { day: today, amount }),
+    }
+
+    store_sender_volume(storage, sender, asset, &volumes)
+}
+
+// 获取手续费币种价格，优先使用价格表，其次查询价格预言机
+pub fn query_fee_price_rate(deps: Deps, asset: &String, denom: &String) -> Result<Decimal, ContractError> {
+    if let Some(price) = read_fee_price(deps.storage, asset, denom)? {
+        return Ok(price);
+    }
+
+    match read_price_oracle(deps.storage)? {
+        Some(oracle) => Ok(query_oracle_price(
+            &deps.querier,
+            deps.api.addr_humanize(&oracle)?,
+            asset.clone(),
+            denom.clone()
+        )?),
+        None => Err(ContractError::FeePriceNotFound {}),
+    }
+}
+
+// 查询合约持有的资产余额，支持的Coin查询bank余额，其余按cw20 Token查询
+pub fn query_asset_balance(deps: Deps, account_addr: &Addr, asset: &String) -> StdResult<Uint128> {
+    if read_support_coin_config(deps.storage, asset)?.is_some() {
+        query_balance(&deps.querier, account_addr.clone(), asset.clone())
+    } else {
+        query_token_balance(&deps.querier, deps.api.addr_validate(asset)?, account_addr.clone())
+    }
+}
+
+// 合约中可用于支付的底仓资金
+pub fn query_available_liquidity(deps: Deps, contract_addr: &Addr, asset: &String) -> StdResult<Uint128> {
+    let balance = query_asset_balance(deps, contract_addr, asset)?;
+    Ok(balance.saturating_sub(read_reserved_balance(deps.storage, asset)?))
+}
+
+// LP提供的流动性，可用于发放目标链订单，但custodian不可提取
+fn read_lp_liquidity(storage: &dyn Storage, asset: &String) -> StdResult<Uint128> {
+    Ok(read_lp_pool(storage, asset)?.map(|pool| pool.liquidity).unwrap_or_default())
+}
+
+// 合约中不属于底仓的资金：未划转的手续费、返佣资金池、用户预存的手续费余额、接收方待领取余额、发放失败待重试的转账及精度换算留存的余数
+pub fn read_reserved_balance(storage: &dyn Storage, asset: &String) -> StdResult<Uint128> {
+    Ok(read_accrued_fee(storage, asset)?
+        .checked_add(read_rewards_pool(storage, asset)?)?
+        .checked_add(read_fee_balance_total(storage, asset)?)?
+        .checked_add(read_claimable_total(storage, asset)?)?
+        .checked_add(read_failed_delivery_total(storage, asset)?)?
+        .checked_add(read_retained_dust(storage, asset)?)?)
+}
+
+// 构造转账消息，支持的Coin使用BankMsg并扣除稳定费，其余按cw20 Token处理，返回转账消息及稳定费
+pub fn build_transfer_msg(deps: Deps, asset: &String, recipient: &Addr, amount: Uint128) -> StdResult<(CosmosMsg, Uint128)> {
+    if read_support_coin_config(deps.storage, asset)?.is_some() {
+        let (transfer_coin, tax) = deduct_tax(&deps.querier, Coin {
+            denom: asset.clone(),
+            amount,
+        })?;
+        Ok((CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![transfer_coin],
+        }), tax))
+    } else {
+        Ok((CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_validate(asset)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount
+            })?,
+            funds: vec![]
+        }), Uint128::zero()))
+    }
+}
+
+// 构造携带调用参数的发放消息：Coin随WasmMsg::Execute附带，Token使用cw20 Send
+pub fn build_call_msg(
+    deps: Deps,
+    asset: &String,
+    contract: &Addr,
+    amount: Uint128,
+    payload: Binary,
+) -> StdResult<(CosmosMsg, Uint128)> {
+    if read_support_coin_config(deps.storage, asset)?.is_some() {
+        let (transfer_coin, tax) = deduct_tax(&deps.querier, Coin {
+            denom: asset.clone(),
+            amount,
+        })?;
+        Ok((CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: payload,
+            funds: vec![transfer_coin],
+        }), tax))
+    } else {
+        Ok((CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_validate(asset)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount,
+                msg: payload
+            })?,
+            funds: vec![]
+        }), Uint128::zero()))
+    }
+}
+
+// 构造通过交易对兑换后发放给接收方的消息，交易对不可用或兑换数量不足最少获得数量时返回None
+pub fn build_swap_msg(
+    deps: Deps,
+    env: &Env,
+    asset: &String,
+    swap: &SwapRoute,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Option<(CosmosMsg, Uint128)>> {
+    let pair = deps.api.addr_validate(swap.pair.as_str())?;
+    let (offer_asset, tax) = build_offer_asset(deps, asset, amount)?;
+
+    // 预估兑换数量不足最少获得数量时不兑换
+    let simulation = match query_simulation(&deps.querier, &pair, offer_asset) {
+        Ok(simulation) => simulation,
+        Err(_) => return Ok(None),
+    };
+    if simulation.return_amount < swap.minimum_receive {
+        return Ok(None);
+    }
+
+    // 兑换及最少获得数量检查通过调用本合约在同一SubMsg中执行，实际获得数量不足时兑换回滚
+    let message = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::SwapDelivery {
+            swap: swap.clone(),
+            asset: asset.clone(),
+            amount,
+            to: recipient.to_string()
+        })?,
+        funds: vec![]
+    });
+
+    Ok(Some((message, tax)))
+}
+
+// Coin需先扣除稳定费，交易对实际收到的数量为扣除后的数量
+fn build_offer_asset(deps: Deps, asset: &String, amount: Uint128) -> StdResult<(Asset, Uint128)> {
+    if read_support_coin_config(deps.storage, asset)?.is_some() {
+        let (offer_coin, tax) = deduct_tax(&deps.querier, Coin {
+            denom: asset.clone(),
+            amount,
+        })?;
+        Ok((Asset { info: AssetInfo::NativeToken { denom: asset.clone() }, amount: offer_coin.amount }, tax))
+    } else {
+        Ok((Asset { info: AssetInfo::Token { contract_addr: asset.clone() }, amount }, Uint128::zero()))
+    }
+}
+
+// 查询账户持有的交易对资产数量
+fn query_pair_asset_balance(querier: &QuerierWrapper, asset_info: &AssetInfo, account: Addr) -> StdResult<Uint128> {
+    match asset_info {
+        AssetInfo::NativeToken { denom } => query_balance(querier, account, denom.clone()),
+        AssetInfo::Token { contract_addr } => {
+            query_token_balance(querier, Addr::unchecked(contract_addr.as_str()), account)
+        }
+    }
+}
+
 pub fn assert_not_pause(deps: &DepsMut) -> StdResult<()> {
     // 获取状态
     let status = read_pause(deps.storage)?;
@@ -1034,6 +4390,119 @@ pub fn assert_not_pause(deps: &DepsMut) -> StdResult<()> {
     Ok(())
 }
 
+pub fn assert_not_emergency(deps: &DepsMut, env: &Env) -> Result<(), ContractError> {
+    // 紧急模式下停止接收新的跨链订单
+    if is_emergency(deps.as_ref(), env)? {
+        return Err(ContractError::EmergencyMode {});
+    }
+
+    Ok(())
+}
+
+pub fn assert_fee_denom(deps: &DepsMut, order: &Order) -> Result<(), ContractError> {
+    if let Some(fee_denom) = &order.fee_denom {
+        // 手续费币种必须是支持的Coin且不同于跨链资产
+        if *fee_denom == order.asset || read_support_coin_config(deps.storage, fee_denom)?.is_none() {
+            return Err(ContractError::InvalidFeeDenom {});
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_dst_address(deps: &DepsMut, order: &Order) -> Result<(), ContractError> {
+    if let Some(format) = read_chain_address_format(deps.storage, order.dst_chain_id)? {
+        if !is_valid_address(&format, &order.to) {
+            return Err(ContractError::InvalidDstAddress { format: format!("{:?}", format) });
+        }
+    }
+
+    Ok(())
+}
+
+// 目标链订单发放前的校验，批量确认时校验失败的订单可跳过
+pub fn assert_confirm_order(deps: &DepsMut, order: &mut ConfirmOrder) -> Result<(), ContractError> {
+    assert_payload(deps, order)?;
+    assert_swap(deps, order)?;
+    resolve_confirm_amount(deps.storage, order)?;
+    deps.api.addr_canonicalize(order.to.as_str())?;
+
+    // 检查订单状态
+    if read_dst_order_state(deps.storage, &order.order_id)?.is_some() {
+        return Err(ContractError::DstOrderAlreadyExist {});
+    }
+
+    // 来源链订单哈希为32字节，且同一来源链订单只能确认一次
+    if let Some(order_hash) = &order.order_hash {
+        if order_hash.len() != 32 {
+            return Err(ContractError::InvalidOrderHash {});
+        }
+        if read_dst_order_hash(deps.storage, order_hash)?.is_some() {
+            return Err(ContractError::DstOrderAlreadyExist {});
+        }
+    }
+
+    // 来源链交易需同时传入来源链id，且同一来源链交易中的订单只能确认一次
+    if let Some(src_tx_hash) = &order.src_tx_hash {
+        let src_chain_id = match order.src_chain_id {
+            Some(src_chain_id) => src_chain_id,
+            None => return Err(ContractError::InvalidSrcTx {}),
+        };
+        if src_tx_hash.is_empty() || src_tx_hash.len() > 128 {
+            return Err(ContractError::InvalidSrcTx {});
+        }
+        if read_dst_src_tx(deps.storage, src_chain_id, src_tx_hash, order.src_event_index)?.is_some() {
+            return Err(ContractError::DstOrderAlreadyExist {});
+        }
+    }
+
+    Ok(())
+}
+
+// 源链订单退款前的校验，批量退款时校验失败的订单可跳过
+pub fn assert_refund_order(deps: &DepsMut, refund: &RefundOrder) -> Result<(), ContractError> {
+    deps.api.addr_canonicalize(refund.from.as_str())?;
+    refund.amount.checked_sub(refund.gas_fee)?;
+
+    // 检查订单状态
+    match read_src_order_state(deps.storage, &refund.order_id)? {
+        Some(state) if state == Uint128::from(1u128) => Ok(()),
+        Some(_) => Err(ContractError::SrcOrderNotSuccess {}),
+        None => Err(ContractError::SrcOrderNotExist {}),
+    }
+}
+
+pub fn assert_payload(deps: &DepsMut, order: &ConfirmOrder) -> Result<(), ContractError> {
+    if order.payload.is_some() {
+        // 合约调用仅支持直接发放，且接收方需在可调用合约列表中
+        if order.delivery_mode != DeliveryMode::Push {
+            return Err(ContractError::InvalidPayload {});
+        }
+        if !read_call_allowlist(deps.storage, &deps.api.addr_canonicalize(order.to.as_str())?)? {
+            return Err(ContractError::NotInCallAllowlist {});
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_swap(deps: &DepsMut, order: &ConfirmOrder) -> Result<(), ContractError> {
+    if let Some(swap) = &order.swap {
+        // 兑换仅支持直接发放且不能同时调用合约
+        if order.delivery_mode != DeliveryMode::Push || order.payload.is_some() {
+            return Err(ContractError::InvalidSwap {});
+        }
+        // 交易对需已登记且包含跨链资产
+        let pair_raw = deps.api.addr_canonicalize(swap.pair.as_str())?;
+        match read_swap_pair(deps.storage, &pair_raw)? {
+            Some(assets) if assets.contains(&order.asset) => {},
+            _ => return Err(ContractError::InvalidSwapPair {}),
+        }
+    }
+
+    Ok(())
+}
+
 pub fn assert_sent_coin_balance(info: &MessageInfo, asset: &String, amount: &Uint128) -> StdResult<()> {
     match info.funds.iter().find(|x| x.denom == *asset) {
         Some(coin) => {
@@ -1065,6 +4534,18 @@ pub fn assert_relayer(deps: &DepsMut, info: &MessageInfo) -> StdResult<()> {
     Ok(())
 }
 
+pub fn assert_treasury(deps: &DepsMut, sender: &Addr) -> Result<(), ContractError> {
+    // 获取配置
+    let config: Config = read_config(deps.storage)?;
+
+    // 检查是否是treasury
+    if deps.api.addr_canonicalize(sender.as_str())? != config.treasury {
+        return Err(ContractError::UnauthorizedTreasuryOnly {});
+    }
+
+    Ok(())
+}
+
 pub fn assert_custodian(deps: &DepsMut, info: &MessageInfo) -> StdResult<()> {
     // 获取配置
     let config: Config = read_config(deps.storage)?;
@@ -1088,3 +4569,499 @@ pub fn assert_risk_control(deps: &DepsMut, info: &MessageInfo) -> StdResult<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{attr, from_slice, Empty, OwnedDeps, Querier, QuerierResult, SystemError, SystemResult, Timestamp};
+    use crate::pair::{PairInfo, PairQueryMsg, SimulationResponse};
+    use crate::state::WrappedSupply;
+
+    const PAIR: &str = "pair0000";
+    const OFFER: &str = "offer0000";
+    const ASK: &str = "ask0000";
+    const RECIPIENT: &str = "recipient";
+
+    // 模拟交易对及兑换资产合约的查询，return_amount为空时模拟交易对查询失败
+    struct PairMockQuerier {
+        base: MockQuerier,
+        return_amount: Option<Uint128>,
+        ask_balance: Uint128,
+    }
+
+    impl Querier for PairMockQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = match from_slice(bin_request) {
+                Ok(request) => request,
+                Err(e) => return SystemResult::Err(SystemError::InvalidRequest {
+                    error: e.to_string(),
+                    request: bin_request.into(),
+                }),
+            };
+            let (contract_addr, msg) = match &request {
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => (contract_addr.as_str(), msg),
+                _ => return self.base.handle_query(&request),
+            };
+
+            let response = match contract_addr {
+                PAIR => match (from_binary(msg).unwrap(), self.return_amount) {
+                    (PairQueryMsg::Pair {}, _) => to_binary(&PairInfo {
+                        asset_infos: [
+                            AssetInfo::Token { contract_addr: OFFER.to_string() },
+                            AssetInfo::Token { contract_addr: ASK.to_string() },
+                        ]
+                    }),
+                    (PairQueryMsg::Simulation { .. }, Some(return_amount)) => to_binary(&SimulationResponse {
+                        return_amount,
+                        spread_amount: Uint128::zero(),
+                        commission_amount: Uint128::zero()
+                    }),
+                    (PairQueryMsg::Simulation { .. }, None) => {
+                        return SystemResult::Ok(ContractResult::Err("pool is empty".to_string()))
+                    },
+                },
+                ASK => to_binary(&Cw20BalanceResponse { balance: self.ask_balance }),
+                _ => return SystemResult::Err(SystemError::NoSuchContract { addr: contract_addr.to_string() }),
+            };
+            SystemResult::Ok(response.into())
+        }
+    }
+
+    fn mock_deps(return_amount: Option<Uint128>, ask_balance: u128) -> OwnedDeps<MockStorage, MockApi, PairMockQuerier> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: PairMockQuerier {
+                base: MockQuerier::new(&[]),
+                return_amount,
+                ask_balance: Uint128::from(ask_balance),
+            },
+        }
+    }
+
+    fn swap_route(minimum_receive: u128) -> SwapRoute {
+        SwapRoute { pair: PAIR.to_string(), minimum_receive: Uint128::from(minimum_receive) }
+    }
+
+    fn swap_delivery_record(deps: Deps) -> Delivery {
+        Delivery {
+            order_id: Uint128::from(1u128),
+            leg: DeliveryLeg::Principal,
+            asset: OFFER.to_string(),
+            to: deps.api.addr_canonicalize(RECIPIENT).unwrap(),
+            amount: Uint128::from(1000u128),
+            payload: None,
+            swap: Some(swap_route(900))
+        }
+    }
+
+    #[test]
+    fn swap_when_simulation_meets_minimum_receive() {
+        let mut deps = mock_deps(Some(Uint128::from(950u128)), 0);
+        let env = mock_env();
+        let delivery = swap_delivery_record(deps.as_ref());
+
+        let (message, tax) = build_delivery_submsg(&mut deps.as_mut(), &env, delivery.clone()).unwrap();
+        assert_eq!(tax, Uint128::zero());
+        assert_eq!(message.msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SwapDelivery {
+                swap: swap_route(900),
+                asset: OFFER.to_string(),
+                amount: Uint128::from(1000u128),
+                to: RECIPIENT.to_string()
+            }).unwrap(),
+            funds: vec![]
+        }));
+        assert_eq!(read_delivery_reply(&deps.storage, message.id).unwrap(), Some(delivery));
+    }
+
+    #[test]
+    fn transfer_when_simulation_below_minimum_receive() {
+        let mut deps = mock_deps(Some(Uint128::from(899u128)), 0);
+        let delivery = swap_delivery_record(deps.as_ref());
+
+        let (message, _) = build_delivery_submsg(&mut deps.as_mut(), &mock_env(), delivery).unwrap();
+        assert_eq!(message.msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: OFFER.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: RECIPIENT.to_string(),
+                amount: Uint128::from(1000u128)
+            }).unwrap(),
+            funds: vec![]
+        }));
+        // 改为发放原资产后回调记录不再携带兑换路径
+        assert_eq!(read_delivery_reply(&deps.storage, message.id).unwrap().unwrap().swap, None);
+    }
+
+    #[test]
+    fn transfer_when_simulation_fails() {
+        let deps = mock_deps(None, 0);
+        let recipient = Addr::unchecked(RECIPIENT);
+
+        let message = build_swap_msg(deps.as_ref(), &mock_env(), &OFFER.to_string(), &swap_route(0), &recipient, Uint128::from(1000u128)).unwrap();
+        assert_eq!(message, None);
+    }
+
+    #[test]
+    fn swap_delivery_sends_swap_and_minimum_receive_check() {
+        let mut deps = mock_deps(Some(Uint128::from(950u128)), 50);
+        let env = mock_env();
+        let msg = ExecuteMsg::SwapDelivery {
+            swap: swap_route(900),
+            asset: OFFER.to_string(),
+            amount: Uint128::from(1000u128),
+            to: RECIPIENT.to_string()
+        };
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info(RECIPIENT, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info(env.contract.address.as_str(), &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: OFFER.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: PAIR.to_string(),
+                amount: Uint128::from(1000u128),
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread: None,
+                    to: Some(RECIPIENT.to_string())
+                }).unwrap()
+            }).unwrap(),
+            funds: vec![]
+        }));
+        assert_eq!(res.messages[1].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: AssetInfo::Token { contract_addr: ASK.to_string() },
+                prev_balance: Uint128::from(50u128),
+                minimum_receive: Uint128::from(900u128),
+                receiver: RECIPIENT.to_string()
+            }).unwrap(),
+            funds: vec![]
+        }));
+    }
+
+    #[test]
+    fn assert_minimum_receive_checks_received_amount() {
+        let env = mock_env();
+        let info = mock_info(env.contract.address.as_str(), &[]);
+        let msg = ExecuteMsg::AssertMinimumReceive {
+            asset_info: AssetInfo::Token { contract_addr: ASK.to_string() },
+            prev_balance: Uint128::from(50u128),
+            minimum_receive: Uint128::from(900u128),
+            receiver: RECIPIENT.to_string()
+        };
+
+        let mut deps = mock_deps(None, 949);
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MinimumReceiveNotMet {}));
+
+        let mut deps = mock_deps(None, 950);
+        execute(deps.as_mut(), env, info, msg).unwrap();
+    }
+
+    #[test]
+    fn failed_swap_falls_back_to_transfer() {
+        let mut deps = mock_deps(Some(Uint128::from(950u128)), 0);
+        let delivery = swap_delivery_record(deps.as_ref());
+        store_delivery_reply(&mut deps.storage, 100, &delivery).unwrap();
+
+        let res = reply(deps.as_mut(), mock_env(), Reply {
+            id: 100,
+            result: ContractResult::Err("Swap Return Less Than Minimum Receive".to_string())
+        }).unwrap();
+        assert_eq!(read_delivery_reply(&deps.storage, 100).unwrap(), None);
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: OFFER.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: RECIPIENT.to_string(),
+                amount: delivery.amount
+            }).unwrap(),
+            funds: vec![]
+        }));
+
+        // 直接转账仍失败时记录该笔转账以便重试
+        let fallback_id = res.messages[0].id;
+        assert_eq!(read_delivery_reply(&deps.storage, fallback_id).unwrap().unwrap().swap, None);
+        reply(deps.as_mut(), mock_env(), Reply {
+            id: fallback_id,
+            result: ContractResult::Err("transfer failed".to_string())
+        }).unwrap();
+        assert_eq!(read_failed_delivery(&deps.storage, &delivery.order_id, &delivery.leg).unwrap().unwrap().amount, delivery.amount);
+        assert_eq!(read_failed_delivery_total(&deps.storage, &delivery.asset).unwrap(), delivery.amount);
+        assert!(read_dst_delivery_failed(&deps.storage, &delivery.order_id).unwrap());
+    }
+
+    // 按链上顺序执行兑换发放的子消息：合约自调用SwapDelivery，交易对按executed_return发放兑换所得给接收方，
+    // 再检查最少收到数量，任一步失败则整体回滚并以失败结果回调
+    fn run_swap_delivery(
+        deps: &mut OwnedDeps<MockStorage, MockApi, PairMockQuerier>,
+        delivery: Delivery,
+        executed_return: u128,
+    ) -> Response {
+        let env = mock_env();
+        let contract_info = mock_info(env.contract.address.as_str(), &[]);
+        let (message, _) = build_delivery_submsg(&mut deps.as_mut(), &env, delivery).unwrap();
+        let prev_balance = deps.querier.ask_balance;
+
+        let mut pending = vec![message.msg.clone()];
+        let mut result = Ok(());
+        while !pending.is_empty() && result.is_ok() {
+            let (contract_addr, msg) = match pending.remove(0) {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => (contract_addr, msg),
+                other => panic!("unexpected message {:?}", other),
+            };
+            if contract_addr == env.contract.address.as_str() {
+                match execute(deps.as_mut(), env.clone(), contract_info.clone(), from_binary(&msg).unwrap()) {
+                    Ok(res) => pending.extend(res.messages.into_iter().map(|message| message.msg)),
+                    Err(err) => result = Err(err.to_string()),
+                }
+                continue;
+            }
+
+            // 模拟交易对收到cw20 Send后发放兑换所得
+            match from_binary(&msg).unwrap() {
+                Cw20ExecuteMsg::Send { contract, msg, .. } if contract == PAIR => {
+                    let PairCw20HookMsg::Swap { to, .. } = from_binary(&msg).unwrap();
+                    assert_eq!(to, Some(RECIPIENT.to_string()));
+                    deps.querier.ask_balance += Uint128::from(executed_return);
+                },
+                other => panic!("unexpected cw20 message {:?}", other),
+            }
+        }
+
+        let result = match result {
+            Ok(()) => ContractResult::Ok(SubMsgExecutionResponse { events: vec![], data: None }),
+            Err(err) => {
+                deps.querier.ask_balance = prev_balance;
+                ContractResult::Err(err)
+            },
+        };
+        reply(deps.as_mut(), env, Reply { id: message.id, result }).unwrap()
+    }
+
+    #[test]
+    fn swap_delivery_against_mock_pair() {
+        let mut deps = mock_deps(Some(Uint128::from(950u128)), 50);
+        let delivery = swap_delivery_record(deps.as_ref());
+
+        let res = run_swap_delivery(&mut deps, delivery, 950);
+        assert!(res.messages.is_empty());
+        assert!(res.attributes.contains(&attr("status", "delivered")));
+        assert_eq!(deps.querier.ask_balance, Uint128::from(1000u128));
+    }
+
+    #[test]
+    fn swap_delivery_below_minimum_receive_falls_back_to_transfer() {
+        // 模拟通过但实际兑换时价格变动，收到数量低于最少收到数量
+        let mut deps = mock_deps(Some(Uint128::from(950u128)), 50);
+        let delivery = swap_delivery_record(deps.as_ref());
+
+        let res = run_swap_delivery(&mut deps, delivery.clone(), 899);
+        assert!(res.attributes.contains(&attr("status", "fallback")));
+        assert_eq!(deps.querier.ask_balance, Uint128::from(50u128));
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: OFFER.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: RECIPIENT.to_string(),
+                amount: delivery.amount
+            }).unwrap(),
+            funds: vec![]
+        }));
+    }
+
+    #[test]
+    fn src_amount_dust_retained_or_rejected() {
+        let mut deps = mock_deps(None, 0);
+        let asset = "uluna".to_string();
+        let mut decimals = ChainDecimals { local_decimals: 18, remote_decimals: 6, dust_rule: DustRule::Retain };
+        store_chain_decimals(&mut deps.storage, &asset, 1, &decimals).unwrap();
+
+        let amount = Uint128::from(1_000_000_000_001u128);
+        let (remote_amount, dust) = convert_src_amount(&mut deps.storage, &asset, 1, amount).unwrap();
+        assert_eq!(remote_amount, Uint256::from(1u128));
+        assert_eq!(dust, Uint128::from(1u128));
+        assert_eq!(read_retained_dust(&deps.storage, &asset).unwrap(), Uint128::from(1u128));
+
+        decimals.dust_rule = DustRule::Reject;
+        store_chain_decimals(&mut deps.storage, &asset, 1, &decimals).unwrap();
+        let err = convert_src_amount(&mut deps.storage, &asset, 1, amount).unwrap_err();
+        assert!(matches!(err, ContractError::DustNotAllowed {}));
+        assert_eq!(read_retained_dust(&deps.storage, &asset).unwrap(), Uint128::from(1u128));
+        convert_src_amount(&mut deps.storage, &asset, 1, Uint128::from(1_000_000_000_000u128)).unwrap();
+    }
+
+    const RISK_CONTROL: &str = "risk0000";
+    const CUSTODIAN: &str = "custodian";
+    const RELAYER: &str = "relayer0000";
+
+    fn instantiate_contract(deps: DepsMut) {
+        instantiate(deps, mock_env(), mock_info("governor", &[]), InstantiateMsg {
+            treasury: "treasury".to_string(),
+            custodian: CUSTODIAN.to_string(),
+            risk_control: RISK_CONTROL.to_string(),
+            relayer: RELAYER.to_string()
+        }).unwrap();
+    }
+
+    #[test]
+    fn withdrawal_keeps_lp_liquidity() {
+        let mut deps = mock_deps(None, 0);
+        let env = mock_env();
+        instantiate_contract(deps.as_mut());
+        deps.querier.base.update_balance(env.contract.address.as_str(), vec![Coin::new(1000, "uluna")]);
+        let lp_token = deps.as_ref().api.addr_canonicalize("lptoken0000").unwrap();
+        store_lp_pool(&mut deps.storage, &"uluna".to_string(), &LpPool {
+            lp_token,
+            total_share: Uint128::from(600u128),
+            liquidity: Uint128::from(600u128),
+            fee_share: Uint128::zero(),
+            permissionless: false
+        }).unwrap();
+
+        for (sender, msg) in [
+            (RISK_CONTROL, ExecuteMsg::WithdrawalPunishCoin { asset: "uluna".to_string(), amount: Uint128::from(401u128) }),
+            (CUSTODIAN, ExecuteMsg::WithdrawalCoin { asset: "uluna".to_string(), amount: Uint128::from(401u128) }),
+        ] {
+            let err = execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap_err();
+            assert!(matches!(err, ContractError::NotEnoughBalance {}));
+        }
+
+        let msg = ExecuteMsg::WithdrawalPunishCoin { asset: "uluna".to_string(), amount: Uint128::from(400u128) };
+        let res = execute(deps.as_mut(), env, mock_info(RISK_CONTROL, &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: RISK_CONTROL.to_string(),
+            amount: vec![Coin::new(400, "uluna")],
+        }));
+    }
+
+    #[test]
+    fn mint_refund_reverses_burn_without_cap() {
+        let mut deps = mock_deps(None, 0);
+        let token = deps.as_ref().api.addr_canonicalize(ASK).unwrap();
+        let order_id = Uint128::from(1u128);
+        // 跨出1000，其中手续费100及余数5留存在合约中，实际销毁895
+        store_src_order(&mut deps.storage, &order_id, &SrcOrder {
+            asset: ASK.to_string(),
+            from: token.clone(),
+            amount: Uint128::from(1000u128),
+            gas_fee: Uint128::from(10u128),
+            deadline: 0,
+            burned: Uint128::from(895u128)
+        }).unwrap();
+        store_wrapped_supply(&mut deps.storage, &token, &WrappedSupply {
+            supply_cap: Some(Uint128::from(5000u128)),
+            total_minted: Uint128::from(5000u128),
+            total_burned: Uint128::from(895u128)
+        }).unwrap();
+
+        let recipient = Addr::unchecked(RECIPIENT);
+        let (message, remaining) = refund_burned(&mut deps.storage, &order_id, &token, ASK, &recipient, Uint128::from(990u128)).unwrap();
+        assert_eq!(message, Some(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ASK.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: RECIPIENT.to_string(), amount: Uint128::from(895u128) }).unwrap(),
+            funds: vec![]
+        })));
+        assert_eq!(remaining, Uint128::from(95u128));
+        let supply = read_wrapped_supply(&deps.storage, &token).unwrap();
+        assert_eq!(supply.total_minted, Uint128::from(5000u128));
+        assert_eq!(supply.total_burned, Uint128::zero());
+    }
+
+    #[test]
+    fn asset_mode_locked_with_outstanding_supply() {
+        let mut deps = mock_deps(None, 0);
+        instantiate_contract(deps.as_mut());
+        let token = deps.as_ref().api.addr_canonicalize(ASK).unwrap();
+        store_support_token_config(&mut deps.storage, &token, &Uint128::zero()).unwrap();
+        store_asset_mode(&mut deps.storage, &token, &AssetMode::MintBurn).unwrap();
+        store_wrapped_supply(&mut deps.storage, &token, &WrappedSupply {
+            supply_cap: Some(Uint128::from(5000u128)),
+            total_minted: Uint128::from(1000u128),
+            total_burned: Uint128::from(400u128)
+        }).unwrap();
+
+        let msg = ExecuteMsg::SetAssetMode { asset: ASK.to_string(), mode: AssetMode::LockRelease, supply_cap: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("governor", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AssetModeInUse {}));
+
+        let mut supply = read_wrapped_supply(&deps.storage, &token).unwrap();
+        supply.total_burned = supply.total_minted;
+        store_wrapped_supply(&mut deps.storage, &token, &supply).unwrap();
+        let msg = ExecuteMsg::SetAssetMode { asset: ASK.to_string(), mode: AssetMode::LockRelease, supply_cap: None };
+        execute(deps.as_mut(), mock_env(), mock_info("governor", &[]), msg).unwrap();
+        assert_eq!(read_asset_mode(&deps.storage, &token).unwrap(), AssetMode::LockRelease);
+        assert_eq!(read_wrapped_supply(&deps.storage, &token).unwrap().supply_cap, None);
+    }
+
+    #[test]
+    fn emergency_reclaim_requires_deadline_and_processed_order() {
+        let mut deps = mock_deps(None, 0);
+        instantiate_contract(deps.as_mut());
+        let from = deps.as_ref().api.addr_canonicalize("sender").unwrap();
+        for (order_id, deadline) in [(1u128, 900u64), (2, 901), (3, 900)] {
+            let order_id = Uint128::from(order_id);
+            store_src_order_state(&mut deps.storage, &order_id, &Uint128::from(1u128)).unwrap();
+            store_src_order(&mut deps.storage, &order_id, &SrcOrder {
+                asset: ASK.to_string(),
+                from: from.clone(),
+                amount: Uint128::from(1000u128),
+                gas_fee: Uint128::from(10u128),
+                deadline,
+                burned: Uint128::zero()
+            }).unwrap();
+        }
+        let msg = ExecuteMsg::SetHeartbeatTimeout { timeout: 100 };
+        execute(deps.as_mut(), mock_env(), mock_info("governor", &[]), msg).unwrap();
+
+        // relayer在1000时最后一次心跳，确认已处理到订单2
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1000);
+        let msg = ExecuteMsg::Heartbeat { checkpoint: None, delivered: vec![], processed: Some(Uint128::from(2u128)) };
+        execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+
+        env.block.time = Timestamp::from_seconds(1101);
+        // 截止时间加超时时间晚于最后一次心跳
+        let msg = ExecuteMsg::EmergencyReclaim { order_id: Uint128::from(2u128) };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("sender", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::ReclaimNotAvailable {}));
+        // 超出relayer已处理到的订单号
+        let msg = ExecuteMsg::EmergencyReclaim { order_id: Uint128::from(3u128) };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("sender", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::ReclaimNotAvailable {}));
+
+        // 截止时间加超时时间恰好等于最后一次心跳
+        let msg = ExecuteMsg::EmergencyReclaim { order_id: Uint128::from(1u128) };
+        let res = execute(deps.as_mut(), env, mock_info("sender", &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: ASK.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "sender".to_string(), amount: Uint128::from(990u128) }).unwrap(),
+            funds: vec![]
+        }));
+        assert_eq!(read_src_order_state(&deps.storage, &Uint128::from(1u128)).unwrap(), Some(Uint128::from(2u128)));
+    }
+
+    #[test]
+    fn heartbeat_keeps_delivered_orders_from_reclaim() {
+        let mut deps = mock_deps(None, 0);
+        instantiate_contract(deps.as_mut());
+        let order_id = Uint128::from(1u128);
+        store_src_order_state(&mut deps.storage, &order_id, &Uint128::from(1u128)).unwrap();
+
+        let msg = ExecuteMsg::Heartbeat { checkpoint: None, delivered: vec![order_id], processed: Some(Uint128::from(5u128)) };
+        execute(deps.as_mut(), mock_env(), mock_info(RELAYER, &[]), msg).unwrap();
+        assert_eq!(read_src_order_state(&deps.storage, &order_id).unwrap(), Some(Uint128::from(3u128)));
+        assert_eq!(read_processed_order(&deps.storage).unwrap(), Some(Uint128::from(5u128)));
+
+        // 已处理到的订单号不会回退
+        let msg = ExecuteMsg::Heartbeat { checkpoint: None, delivered: vec![], processed: Some(Uint128::from(4u128)) };
+        execute(deps.as_mut(), mock_env(), mock_info(RELAYER, &[]), msg).unwrap();
+        assert_eq!(read_processed_order(&deps.storage).unwrap(), Some(Uint128::from(5u128)));
+    }
+}
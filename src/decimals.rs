@@ -0,0 +1,92 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{StdError, StdResult, Uint128, Uint256};
+
+use crate::msg::ChainDecimals;
+
+fn pow10(exp: u8) -> StdResult<Uint256> {
+    Ok(Uint256::from(10u8).checked_pow(exp as u32)?)
+}
+
+pub fn to_uint128(value: Uint256) -> StdResult<Uint128> {
+    Uint128::try_from(value).map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+// 本链数量转换为目标链精度的数量，精度降低时向下取整，返回转换后的数量及无法表示的本链余数
+pub fn to_remote_amount(amount: Uint128, decimals: &ChainDecimals) -> StdResult<(Uint256, Uint128)> {
+    let amount = Uint256::from(amount);
+    if decimals.remote_decimals >= decimals.local_decimals {
+        let factor = pow10(decimals.remote_decimals - decimals.local_decimals)?;
+        Ok((amount.checked_mul(factor)?, Uint128::zero()))
+    } else {
+        let factor = pow10(decimals.local_decimals - decimals.remote_decimals)?;
+        let dust = amount.checked_rem(factor)?;
+        Ok((amount.checked_div(factor)?, to_uint128(dust)?))
+    }
+}
+
+// 来源链数量转换为本链精度的数量，精度降低时向下取整，返回转换后的数量及无法表示的来源链余数
+pub fn to_local_amount(remote_amount: Uint256, decimals: &ChainDecimals) -> StdResult<(Uint128, Uint256)> {
+    if decimals.remote_decimals >= decimals.local_decimals {
+        let factor = pow10(decimals.remote_decimals - decimals.local_decimals)?;
+        let dust = remote_amount.checked_rem(factor)?;
+        Ok((to_uint128(remote_amount.checked_div(factor)?)?, dust))
+    } else {
+        let factor = pow10(decimals.local_decimals - decimals.remote_decimals)?;
+        Ok((to_uint128(remote_amount.checked_mul(factor)?)?, Uint256::zero()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::DustRule;
+
+    fn decimals(local_decimals: u8, remote_decimals: u8) -> ChainDecimals {
+        ChainDecimals { local_decimals, remote_decimals, dust_rule: DustRule::Retain }
+    }
+
+    #[test]
+    fn local_6_remote_18() {
+        let decimals = decimals(6, 18);
+        assert_eq!(
+            to_remote_amount(Uint128::from(1_234_567u128), &decimals).unwrap(),
+            (Uint256::from(1_234_567_000_000_000_000u128), Uint128::zero())
+        );
+        // 来源链低于本链最小单位的部分为余数
+        assert_eq!(
+            to_local_amount(Uint256::from(1_234_567_000_000_000_001u128), &decimals).unwrap(),
+            (Uint128::from(1_234_567u128), Uint256::from(1u128))
+        );
+        assert_eq!(
+            to_local_amount(Uint256::from(1_234_567_000_000_000_000u128), &decimals).unwrap(),
+            (Uint128::from(1_234_567u128), Uint256::zero())
+        );
+    }
+
+    #[test]
+    fn local_18_remote_6() {
+        let decimals = decimals(18, 6);
+        // 本链低于目标链最小单位的部分为余数
+        assert_eq!(
+            to_remote_amount(Uint128::from(1_234_567_000_000_000_001u128), &decimals).unwrap(),
+            (Uint256::from(1_234_567u128), Uint128::from(1u128))
+        );
+        assert_eq!(
+            to_remote_amount(Uint128::from(1_234_567_000_000_000_000u128), &decimals).unwrap(),
+            (Uint256::from(1_234_567u128), Uint128::zero())
+        );
+        assert_eq!(
+            to_local_amount(Uint256::from(1_234_567u128), &decimals).unwrap(),
+            (Uint128::from(1_234_567_000_000_000_000u128), Uint256::zero())
+        );
+    }
+
+    #[test]
+    fn local_amount_overflow() {
+        // 超过Uint128::MAX的来源链数量报错而不是截断
+        let remote_amount = Uint256::from(u128::MAX).checked_add(Uint256::from(1u8)).unwrap();
+        assert!(to_local_amount(remote_amount, &decimals(6, 6)).is_err());
+        assert!(to_local_amount(Uint256::from(u128::MAX), &decimals(18, 6)).is_err());
+    }
+}
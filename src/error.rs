@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +6,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("Unauthorized: Only Governor can call")]
     Unauthorized {},
 
@@ -18,6 +21,12 @@ pub enum ContractError {
     #[error("Unauthorized: Only Risk Control can call")]
     UnauthorizedRiskControl {},
 
+    #[error("Unauthorized: Only Treasury Or Governor can call")]
+    UnauthorizedTreasury {},
+
+    #[error("Unauthorized: Only Treasury can call")]
+    UnauthorizedTreasuryOnly {},
+
     #[error("Invalid: Invalid Cw20 Msg")]
     InvalidCw20Msg {},
 
@@ -50,4 +59,124 @@ pub enum ContractError {
 
     #[error("Invalid: Not Enough Balance To Withdraw")]
     NotEnoughBalance {},
+
+    #[error("Invalid: Unpaid Rewards Not Exist")]
+    UnpaidRewardsNotExist {},
+
+    #[error("Invalid: Rewards Pool Is Empty")]
+    RewardsPoolEmpty {},
+
+    #[error("Invalid: Invalid Fee Tiers")]
+    InvalidFeeTiers {},
+
+    #[error("Invalid: Exceed Max Fee Rate")]
+    ExceedMaxFeeRate {},
+
+    #[error("Invalid: Invalid Fee Denom")]
+    InvalidFeeDenom {},
+
+    #[error("Invalid: Fee Price Not Found")]
+    FeePriceNotFound {},
+
+    #[error("Invalid: Not Enough Fee Balance")]
+    NotEnoughFeeBalance {},
+
+    #[error("Invalid: Non Standard Token")]
+    NonStandardToken {},
+
+    #[error("Invalid: Token Balance Less Then Received Amount")]
+    TokenBalanceMismatch {},
+
+    #[error("Invalid: Lp Pool Not Exist")]
+    LpPoolNotExist {},
+
+    #[error("Invalid: Lp Pool Already Exist")]
+    LpPoolAlreadyExist {},
+
+    #[error("Invalid: Lp Token Minter Must Be This Contract")]
+    InvalidLpToken {},
+
+    #[error("Unauthorized: Provider Not In Lp Allowlist")]
+    NotInLpAllowlist {},
+
+    #[error("Invalid: Not Enough Available Liquidity")]
+    NotEnoughLiquidity {},
+
+    #[error("Invalid: Failed Delivery Not Exist")]
+    FailedDeliveryNotExist {},
+
+    #[error("Unauthorized: Contract Not In Call Allowlist")]
+    NotInCallAllowlist {},
+
+    #[error("Invalid: Payload Only Supported For Push Delivery")]
+    InvalidPayload {},
+
+    #[error("Invalid: Swap Pair Not Registered For Asset")]
+    InvalidSwapPair {},
+
+    #[error("Invalid: Swap Only Supported For Push Delivery Without Payload")]
+    InvalidSwap {},
+
+    #[error("Invalid: Swap Return Less Than Minimum Receive")]
+    MinimumReceiveNotMet {},
+
+    #[error("Invalid: Wrapped Token Minter Must Be This Contract")]
+    InvalidWrappedToken {},
+
+    #[error("Invalid: Exceed Wrapped Token Supply Cap")]
+    ExceedSupplyCap {},
+
+    #[error("Invalid: Asset Mode Cannot Change With Outstanding Supply Or Liquidity")]
+    AssetModeInUse {},
+
+    #[error("Invalid: Amount Not Representable In Target Decimals")]
+    DustNotAllowed {},
+
+    #[error("Invalid: Remote Amount Mismatch")]
+    InvalidRemoteAmount {},
+
+    #[error("Invalid: Route To Destination Chain Not Exist")]
+    RouteNotExist {},
+
+    #[error("Invalid: Destination Address Not Match {format} Format")]
+    InvalidDstAddress { format: String },
+
+    #[error("Invalid: Refund Not Available Before Deadline And Timeout")]
+    RefundNotAvailable {},
+
+    #[error("Invalid: Refund Request Already Exist")]
+    RefundRequestAlreadyExist {},
+
+    #[error("Invalid: Refund Request Not Exist Or Already Answered")]
+    RefundRequestNotPending {},
+
+    #[error("Invalid: Refund Response Window Not Passed")]
+    RefundWindowNotPassed {},
+
+    #[error("Invalid: Refund Response Window Passed")]
+    RefundWindowPassed {},
+
+    #[error("Invalid: Relayer Heartbeat Stale, Emergency Mode Active")]
+    EmergencyMode {},
+
+    #[error("Invalid: Emergency Mode Not Active")]
+    NotEmergencyMode {},
+
+    #[error("Invalid: Reclaim Not Available Before Deadline Reported By Relayer")]
+    ReclaimNotAvailable {},
+
+    #[error("Invalid: Batch Size")]
+    InvalidBatchSize {},
+
+    #[error("Invalid: Signature")]
+    InvalidSignature {},
+
+    #[error("Invalid: Nonce")]
+    InvalidNonce {},
+
+    #[error("Invalid: Order Hash")]
+    InvalidOrderHash {},
+
+    #[error("Invalid: Source Transaction")]
+    InvalidSrcTx {},
 }
@@ -0,0 +1,132 @@
+use cosmwasm_std::Uint128;
+use sha3::{Digest, Keccak256};
+
+use crate::msg::Order;
+
+// 订单哈希与EVM合约一致，为以下参数按abi.encode编码后的keccak256：
+// (uint256 orderId, string asset, string from, string to, uint256 amount, uint256 gasFee,
+//  uint256 crossChainFee, uint256 rewards, uint256 srcChainId, uint256 dstChainId,
+//  uint256 deadline, string channel)
+// 手续费支付币种仅在本链使用，不参与哈希，测试向量见tests
+
+// ABI编码的参数
+enum Token<'a> {
+    Uint(u128),
+    String(&'a str),
+}
+
+fn encode_uint(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+// 静态参数直接写入头部；动态参数在头部写入相对编码起点的偏移量，在尾部写入长度及补零到32字节整数倍的内容
+fn abi_encode(tokens: &[Token]) -> Vec<u8> {
+    let head_len = 32 * tokens.len();
+    let mut head: Vec<u8> = Vec::with_capacity(head_len);
+    let mut tail: Vec<u8> = vec![];
+    for token in tokens {
+        match token {
+            Token::Uint(value) => head.extend_from_slice(&encode_uint(*value)),
+            Token::String(value) => {
+                head.extend_from_slice(&encode_uint((head_len + tail.len()) as u128));
+                tail.extend_from_slice(&encode_uint(value.len() as u128));
+                tail.extend_from_slice(value.as_bytes());
+                tail.resize(tail.len() + (32 - value.len() % 32) % 32, 0);
+            }
+        }
+    }
+
+    head.extend(tail);
+    head
+}
+
+// 订单的ABI编码
+pub fn encode_order(order_id: Uint128, order: &Order) -> Vec<u8> {
+    abi_encode(&[
+        Token::Uint(order_id.u128()),
+        Token::String(&order.asset),
+        Token::String(&order.from),
+        Token::String(&order.to),
+        Token::Uint(order.amount.u128()),
+        Token::Uint(order.gas_fee.u128()),
+        Token::Uint(order.cross_chain_fee.u128()),
+        Token::Uint(order.rewards.u128()),
+        Token::Uint(order.src_chain_id as u128),
+        Token::Uint(order.dst_chain_id as u128),
+        Token::Uint(order.deadline as u128),
+        Token::String(&order.channel),
+    ])
+}
+
+// 订单哈希
+pub fn order_hash(order_id: Uint128, order: &Order) -> Vec<u8> {
+    Keccak256::digest(&encode_order(order_id, order)).to_vec()
+}
+
+// 0x开头的小写十六进制
+pub fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("0x{}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(channel: &str) -> Order {
+        Order {
+            client_order_id: None,
+            asset: "uluna".to_string(),
+            from: "terra1sender".to_string(),
+            to: "0x000000000000000000000000000000000000dead".to_string(),
+            amount: Uint128::from(1000000u128),
+            gas_fee: Uint128::from(1000u128),
+            cross_chain_fee: Uint128::from(2000u128),
+            rewards: Uint128::zero(),
+            src_chain_id: 1,
+            dst_chain_id: 56,
+            deadline: 1700000000,
+            channel: channel.to_string(),
+            fee_denom: None
+        }
+    }
+
+    fn hash_hex(order: &Order) -> String {
+        to_hex(&order_hash(Uint128::from(1u128), order))
+    }
+
+    #[test]
+    fn order_hash_vector() {
+        assert_eq!(
+            hash_hex(&order("iswap")),
+            "0x1b464d046d2ce8b15db8b2135a142dd92181e80d470f70d1f90f14e449ab273d"
+        );
+    }
+
+    #[test]
+    fn order_hash_empty_string() {
+        // 空字符串只编码长度0，不占用内容字
+        let encoded = encode_order(Uint128::from(1u128), &order(""));
+        assert_eq!(encoded.len(), 640);
+        assert_eq!(&encoded[608..], &[0u8; 32]);
+        assert_eq!(
+            hash_hex(&order("")),
+            "0x6d64d0b54712bf2804cabbd4ed75ed6c62f2aaacdadd782c0d8ad2a7f8f2ca37"
+        );
+    }
+
+    #[test]
+    fn order_hash_32_byte_string() {
+        // 长度恰为32字节的字符串不补零
+        let channel = "0123456789abcdef0123456789abcdef";
+        let encoded = encode_order(Uint128::from(1u128), &order(channel));
+        assert_eq!(encoded.len(), 672);
+        assert_eq!(&encoded[640..], channel.as_bytes());
+        assert_eq!(
+            hash_hex(&order(channel)),
+            "0x6da265419e253ed3b799e5df7e067f7d6deac85d42ab8133f0fbe6b2093c43ca"
+        );
+    }
+}
@@ -1,6 +1,13 @@
+pub mod address;
 pub mod contract;
+pub mod decimals;
+pub mod hash;
 mod error;
 pub mod msg;
+pub mod oracle;
+pub mod pair;
+pub mod signature;
 pub mod state;
+pub mod tax;
 
 pub use crate::error::ContractError;
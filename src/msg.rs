@@ -1,8 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Uint128, Addr};
+use cosmwasm_std::{Uint128, Uint256, Addr, Decimal, Binary};
 use cw20::Cw20ReceiveMsg;
 
+use crate::pair::AssetInfo;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub treasury: String,
@@ -13,8 +15,8 @@ pub struct InstantiateMsg {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Order {
-    // 订单id
-    pub order_id: Uint128,
+    // 客户端传入的订单id，仅作为同一发送方的幂等键，订单id由合约分配
+    pub client_order_id: Option<Uint128>,
     // 跨链资产币种
     pub asset: String,
     // 源链发送方
@@ -36,7 +38,246 @@ pub struct Order {
     // 截止时间
     pub deadline: u64,
     // 渠道商
-    pub channel: String
+    pub channel: String,
+    // 手续费支付币种，为None时从跨链资产中扣除
+    pub fee_denom: Option<String>
+}
+
+// 批量跨链中的单笔订单，跨链资产及发送方由批量消息确定
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositOrder {
+    // 客户端传入的订单id，仅作为同一发送方的幂等键
+    #[serde(default)]
+    pub order_id: Option<Uint128>,
+    // 目标链接收方
+    pub to: String,
+    // 跨链资产数量
+    pub amount: Uint128,
+    // 补贴的gas费用，源链收取
+    pub gas_fee: Uint128,
+    // 跨链手续费
+    pub cross_chain_fee: Uint128,
+    // 最小返佣
+    pub rewards: Uint128,
+    // 源链ChainId
+    pub src_chain_id: u64,
+    // 目标链ChainId
+    pub dst_chain_id: u64,
+    // 截止时间
+    pub deadline: u64,
+    // 渠道商
+    pub channel: String,
+    // 手续费支付币种，从预存的手续费余额中扣除
+    #[serde(default)]
+    pub fee_denom: Option<String>
+}
+
+impl DepositOrder {
+    pub fn into_order(self, asset: String, from: String) -> Order {
+        Order {
+            client_order_id: self.order_id,
+            asset,
+            from,
+            to: self.to,
+            amount: self.amount,
+            gas_fee: self.gas_fee,
+            cross_chain_fee: self.cross_chain_fee,
+            rewards: self.rewards,
+            src_chain_id: self.src_chain_id,
+            dst_chain_id: self.dst_chain_id,
+            deadline: self.deadline,
+            channel: self.channel,
+            fee_denom: self.fee_denom
+        }
+    }
+}
+
+// 用户链下签名的跨链订单，nonce需等于用户当前的nonce
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignedOrder {
+    pub order: Order,
+    pub nonce: u64
+}
+
+// relayer已处理到的源链区块高度
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Checkpoint {
+    pub chain_id: u64,
+    pub height: u64
+}
+
+// 用户退款申请的状态
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundRequestStatus {
+    // 等待relayer或risk_control答复
+    Pending,
+    // 已确认送达，拒绝退款
+    Rejected,
+    // 已退款
+    Refunded,
+}
+
+// 目标链的地址格式
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressFormat {
+    // 0x开头的十六进制地址，支持EIP-55校验
+    Evm,
+    // base58check编码的Tron地址
+    Tron,
+    // base58编码的32字节Solana地址
+    Solana,
+    // 指定前缀的bech32地址
+    Bech32 { prefix: String },
+}
+
+// 精度降低时无法表示的余数的处理方式
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DustRule {
+    // 向下取整，余数留存在合约中
+    #[default]
+    Retain,
+    // 存在余数时拒绝
+    Reject,
+}
+
+// 资产在本链与其他链上的精度
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChainDecimals {
+    pub local_decimals: u8,
+    pub remote_decimals: u8,
+    pub dust_rule: DustRule
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeTier {
+    // 近30天跨链量下限
+    pub volume_min: Uint128,
+    // 最大跨链手续费率，分母为2_000_000
+    pub fee_rate: Uint128
+}
+
+// 目标链订单发放或源链订单退款的转账类型
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryLeg {
+    // 本金
+    Principal,
+    // 返佣
+    Rewards,
+    // 源链订单退款
+    Refund,
+}
+
+impl DeliveryLeg {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryLeg::Principal => "principal",
+            DeliveryLeg::Rewards => "rewards",
+            DeliveryLeg::Refund => "refund",
+        }
+    }
+}
+
+// 目标链订单的发放方式
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMode {
+    // 直接转账给接收方
+    #[default]
+    Push,
+    // 记入接收方的待领取余额，由接收方通过Claim领取
+    Credit,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfirmOrder {
+    // 订单id
+    pub order_id: Uint128,
+    // 跨链资产币种
+    pub asset: String,
+    // 目标链接收方
+    pub to: String,
+    // 跨链资产数量
+    pub amount: Uint128,
+    // 返佣
+    pub rewards: Uint128,
+    // 发放方式
+    #[serde(default)]
+    pub delivery_mode: DeliveryMode,
+    // 接收方合约的调用参数，为空时直接转账
+    #[serde(default)]
+    pub payload: Option<Binary>,
+    // 发放前通过交易对兑换为其他资产，为空时发放原资产
+    #[serde(default)]
+    pub swap: Option<SwapRoute>,
+    // 来源链精度下的跨链数量，不为空时按来源链精度换算本链数量
+    #[serde(default)]
+    pub remote_amount: Option<Uint256>,
+    // 来源链id
+    #[serde(default)]
+    pub src_chain_id: Option<u64>,
+    // 来源链的订单哈希，用于与来源链订单匹配
+    #[serde(default)]
+    pub order_hash: Option<Binary>,
+    // 来源链的交易哈希
+    #[serde(default)]
+    pub src_tx_hash: Option<String>,
+    // 订单在来源链交易中的序号，同一笔交易包含多个订单时区分各订单
+    #[serde(default)]
+    pub src_event_index: u32,
+    // 来源链发送方
+    #[serde(default)]
+    pub src_sender: Option<String>
+}
+
+// 批量退款中的单笔源链订单
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundOrder {
+    // 订单id
+    pub order_id: Uint128,
+    // 跨链资产币种
+    pub asset: String,
+    // 发送方
+    pub from: String,
+    // 跨链资产数量
+    pub amount: Uint128,
+    // 补贴的gas费用，源链收取
+    pub gas_fee: Uint128
+}
+
+// 发放前的兑换路径
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapRoute {
+    // 已登记的交易对合约
+    pub pair: String,
+    // 接收方最少获得的兑换资产数量，不足时发放原资产
+    pub minimum_receive: Uint128
+}
+
+// cw20 Token的转账特性
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenCapability {
+    // 标准cw20，到账数量等于转账数量
+    Standard,
+    // 转账时扣除手续费
+    FeeOnTransfer,
+    // 余额会自动变化
+    Rebasing,
+}
+
+// 资产的跨链方式
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetMode {
+    // 跨出时锁定在合约中，跨入时从底仓释放
+    #[default]
+    LockRelease,
+    // 合约为cw20 minter，跨出时销毁，跨入时铸造
+    MintBurn,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -44,7 +285,8 @@ pub struct Order {
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     CrossChainCoin {
-        order_id: Uint128,
+        #[serde(default)]
+        order_id: Option<Uint128>,
         asset: String,
         to: String,
         amount: Uint128,
@@ -54,21 +296,80 @@ pub enum ExecuteMsg {
         src_chain_id: u64,
         dst_chain_id: u64,
         deadline: u64,
-        channel: String
+        channel: String,
+        #[serde(default)]
+        fee_denom: Option<String>
     },
+    // 一笔交易提交多个Coin跨链订单
+    CrossChainCoinBatch { asset: String, orders: Vec<DepositOrder> },
+    // 用户预先通过IncreaseAllowance授权，由合约TransferFrom转入Token
+    CrossChainTokenFrom {
+        #[serde(default)]
+        order_id: Option<Uint128>,
+        asset: String,
+        to: String,
+        amount: Uint128,
+        gas_fee: Uint128,
+        cross_chain_fee: Uint128,
+        rewards: Uint128,
+        src_chain_id: u64,
+        dst_chain_id: u64,
+        deadline: u64,
+        channel: String,
+        #[serde(default)]
+        fee_denom: Option<String>
+    },
+    // relayer提交用户链下签名的订单，由合约TransferFrom转入用户授权的Token
+    CrossChainTokenSigned { signed: SignedOrder, pubkey: Binary, signature: Binary },
     CrossChainCoinConfirm {
         order_id: Uint128,
         asset: String,
         to: String,
         amount: Uint128,
-        rewards: Uint128
+        rewards: Uint128,
+        #[serde(default)]
+        delivery_mode: DeliveryMode,
+        #[serde(default)]
+        payload: Option<Binary>,
+        #[serde(default)]
+        swap: Option<SwapRoute>,
+        #[serde(default)]
+        remote_amount: Option<Uint256>,
+        #[serde(default)]
+        src_chain_id: Option<u64>,
+        #[serde(default)]
+        order_hash: Option<Binary>,
+        #[serde(default)]
+        src_tx_hash: Option<String>,
+        #[serde(default)]
+        src_event_index: u32,
+        #[serde(default)]
+        src_sender: Option<String>
     },
     CrossChainTokenConfirm {
         order_id: Uint128,
         asset: String,
         to: String,
         amount: Uint128,
-        rewards: Uint128
+        rewards: Uint128,
+        #[serde(default)]
+        delivery_mode: DeliveryMode,
+        #[serde(default)]
+        payload: Option<Binary>,
+        #[serde(default)]
+        swap: Option<SwapRoute>,
+        #[serde(default)]
+        remote_amount: Option<Uint256>,
+        #[serde(default)]
+        src_chain_id: Option<u64>,
+        #[serde(default)]
+        order_hash: Option<Binary>,
+        #[serde(default)]
+        src_tx_hash: Option<String>,
+        #[serde(default)]
+        src_event_index: u32,
+        #[serde(default)]
+        src_sender: Option<String>
     },
     ChangeGovernor { new_governor: String },
     ChangeTreasury { new_treasury: String },
@@ -106,14 +407,87 @@ pub enum ExecuteMsg {
     WithdrawalCoin { asset: String, amount: Uint128 },
     WithdrawalPunishToken { asset: String, amount: Uint128 },
     WithdrawalPunishCoin { asset: String, amount: Uint128 },
+    SweepFees { assets: Vec<String> },
+    // treasury充值返佣资金池，资金随交易发送
+    DepositRewards {},
+    WithdrawRewards { asset: String, amount: Uint128 },
+    PayUnpaidRewards { order_id: Uint128 },
+    // 设置按近30天跨链量分级的手续费率，tiers按volume_min升序排列
+    SetFeeTiers { asset: String, tiers: Vec<FeeTier> },
+    // 设置渠道商的手续费率，为None时取消
+    SetChannelFeeRate { channel: String, fee_rate: Option<Uint128> },
+    // 设置手续费币种价格，price为1个最小单位的asset可兑换的denom最小单位数量，为None时取消
+    SetFeePrice { asset: String, denom: String, price: Option<Decimal> },
+    SetPriceOracle { oracle: Option<String> },
+    // 预存用于支付手续费的Coin，资金随交易发送
+    DepositFeeBalance {},
+    WithdrawFeeBalance { denom: String, amount: Uint128 },
+    // 设置Token的转账特性，非标准Token拒绝跨链
+    SetTokenCapability { asset: String, capability: TokenCapability },
+    // 登记资产的LP Token，LP Token的minter必须是本合约
+    RegisterLpToken { asset: String, lp_token: String },
+    // 设置LP分得跨链手续费的比例(分母为2_000_000)及是否允许任何人提供流动性
+    SetLpConfig { asset: String, fee_share: Uint128, permissionless: bool },
+    SetLpAllowlist { asset: String, provider: String, allowed: bool },
+    // 提供Coin流动性，资金随交易发送
+    ProvideLiquidity { asset: String },
+    // custodian补充Coin底仓，资金随交易发送
+    TopUpLiquidity {},
+    // 使用当前底仓发放待发放队列中的订单
+    ProcessQueue { asset: String, limit: Option<u32> },
+    // 领取待领取余额
+    Claim { assets: Vec<String> },
+    // 重试发放失败的转账
+    RetryDelivery { order_id: Uint128, leg: DeliveryLeg },
+    // 设置目标链订单可调用的合约
+    SetCallAllowlist { contract: String, allowed: bool },
+    // 登记或移除发放时可使用的交易对
+    SetSwapPair { pair: String, enabled: bool },
+    // 发放时通过交易对兑换，仅本合约可调用
+    SwapDelivery { swap: SwapRoute, asset: String, amount: Uint128, to: String },
+    // 检查兑换后接收方获得的数量，仅本合约可调用
+    AssertMinimumReceive { asset_info: AssetInfo, prev_balance: Uint128, minimum_receive: Uint128, receiver: String },
+    // 设置Token的跨链方式及铸造上限
+    SetAssetMode { asset: String, mode: AssetMode, supply_cap: Option<Uint128> },
+    // 设置资产在其他链上的精度，decimals为空时移除
+    SetChainDecimals { asset: String, chain_id: u64, decimals: Option<ChainDecimals> },
+    // 设置资产在目标链上对应的资产，remote_asset为空时移除
+    SetRoute { asset: String, chain_id: u64, remote_asset: Option<String> },
+    // 设置目标链的地址格式，format为空时不检查接收方地址
+    SetChainAddressFormat { chain_id: u64, format: Option<AddressFormat> },
+    // 设置用户申请退款的等待时间及relayer答复的时间窗口，单位为秒
+    SetRefundConfig { timeout: u64, response_window: u64 },
+    // 源链订单超过截止时间及等待时间后，发送方申请退款
+    RequestRefund { order_id: Uint128 },
+    // relayer或risk_control答复退款申请，未送达时立即退款，已送达时附上送达凭证拒绝
+    RespondRefund { order_id: Uint128, delivered: bool, proof: Option<String> },
+    // 答复时间窗口结束后仍未答复的退款申请可自行执行退款
+    ExecuteRefund { order_id: Uint128 },
+    // 设置relayer心跳的超时时间，单位为秒
+    SetHeartbeatTimeout { timeout: u64 },
+    // relayer定期发送心跳，可附带已处理到的源链区块高度、已在目标链送达的源链订单，
+    // 以及已处理到的源链订单号processed，即该订单号及之前的订单中送达的均已通过delivered上报
+    Heartbeat {
+        checkpoint: Option<Checkpoint>,
+        #[serde(default)]
+        delivered: Vec<Uint128>,
+        processed: Option<Uint128>
+    },
+    // 紧急模式下发送方取回未完成的源链订单资产
+    EmergencyReclaim { order_id: Uint128 },
+    // relayer批量确认目标链订单，atomic为true时任一订单失败则整批失败
+    BatchConfirm { orders: Vec<ConfirmOrder>, atomic: bool },
+    // relayer批量退还源链订单，atomic为true时任一订单失败则整批失败
+    BatchRefund { orders: Vec<RefundOrder>, atomic: bool },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     CrossChainToken {
-        // 订单id
-        order_id: Uint128,
+        // 客户端传入的订单id，仅作为同一发送方的幂等键
+        #[serde(default)]
+        order_id: Option<Uint128>,
         // 目标链接收方
         to: String,
         // 补贴的gas费用，源链收取
@@ -129,8 +503,21 @@ pub enum Cw20HookMsg {
         // 截止时间
         deadline: u64,
         // 渠道商
-        channel: String
+        channel: String,
+        // 手续费支付币种，从预存的手续费余额中扣除
+        #[serde(default)]
+        fee_denom: Option<String>
     },
+    // 一笔转账提交多个Token跨链订单
+    CrossChainTokenBatch { orders: Vec<DepositOrder> },
+    // treasury充值返佣资金池
+    DepositRewards {},
+    // 提供Token流动性
+    ProvideLiquidity {},
+    // 将LP Token发送到本合约，赎回流动性
+    WithdrawLiquidity {},
+    // custodian补充Token底仓
+    TopUpLiquidity {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -141,6 +528,35 @@ pub enum QueryMsg {
     QuerySupportCoin { asset: String },
     QuerySrcOrderStatus { order_id: Uint128 },
     QueryDstOrderStatus { order_id: Uint128 },
+    QueryUnsweptFee { asset: String },
+    QueryRewardsPool { asset: String },
+    QueryUnpaidRewards { order_id: Uint128 },
+    QueryFeeTiers { asset: String },
+    QueryFeeQuote { sender: String, asset: String, amount: Uint128, channel: String },
+    QueryFeePrice { asset: String, denom: String },
+    QueryFeeBalance { address: String, denom: String },
+    QueryTokenCapability { asset: String },
+    QueryLpPool { asset: String },
+    QueryPendingDeliveries { asset: String, start_after: Option<u64>, limit: Option<u32> },
+    QueryClaimable { address: String, asset: String },
+    QueryFailedDeliveries { order_id: Uint128 },
+    QueryCallAllowlist { contract: String },
+    QuerySwapPair { pair: String },
+    QueryWrappedSupply { asset: String },
+    QueryChainDecimals { asset: String, chain_id: u64 },
+    QueryRetainedDust { asset: String },
+    QueryRoutes { asset: String },
+    QueryChainAddressFormat { chain_id: u64 },
+    QueryRefundConfig {},
+    QueryRefundRequest { order_id: Uint128 },
+    QueryLiveness {},
+    QueryCheckpoint { chain_id: u64 },
+    QueryNonce { address: String },
+    QueryOrderHash { order_id: Uint128 },
+    QueryDstOrderByHash { order_hash: Binary },
+    QueryDstOrder { order_id: Uint128 },
+    QueryClientOrder { sender: String, client_order_id: Uint128 },
+    QuerySignedOrderDigest { signed: SignedOrder },
 }
 
 
@@ -159,6 +575,239 @@ pub struct QueryOrderStatusResponse {
     pub status: Uint128
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryUnsweptFeeResponse {
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryRewardsPoolResponse {
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryUnpaidRewardsResponse {
+    pub asset: String,
+    pub to: String,
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryFeeTiersResponse {
+    pub tiers: Vec<FeeTier>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryFeeQuoteResponse {
+    // 近30天跨链量
+    pub volume: Uint128,
+    // 生效的最大跨链手续费率
+    pub fee_rate: Uint128,
+    // 本次跨链允许的最大跨链手续费
+    pub max_cross_chain_fee: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryFeePriceResponse {
+    pub price: Decimal
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryFeeBalanceResponse {
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryTokenCapabilityResponse {
+    pub capability: TokenCapability
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryLpPoolResponse {
+    pub lp_token: Addr,
+    // LP份额总量
+    pub total_share: Uint128,
+    // LP提供的流动性及分得的手续费
+    pub liquidity: Uint128,
+    pub fee_share: Uint128,
+    pub permissionless: bool,
+    // 每份LP可赎回的资产数量
+    pub share_price: Decimal,
+    // 合约中当前可用于赎回的资产数量
+    pub available: Uint128,
+    // 流动性利用率
+    pub utilisation: Decimal
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDeliveryResponse {
+    pub index: u64,
+    pub order_id: Uint128,
+    pub to: Addr,
+    pub amount: Uint128,
+    pub rewards: Uint128,
+    pub delivery_mode: DeliveryMode,
+    pub payload: Option<Binary>,
+    pub swap: Option<SwapRoute>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryPendingDeliveriesResponse {
+    pub deliveries: Vec<PendingDeliveryResponse>,
+    // 待发放的总数量
+    pub total_pending: Uint128,
+    // 合约中可用的底仓
+    pub available: Uint128,
+    // 发放全部待发放订单还缺少的底仓
+    pub shortfall: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryClaimableResponse {
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FailedDeliveryResponse {
+    pub leg: DeliveryLeg,
+    pub asset: String,
+    pub to: Addr,
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryCallAllowlistResponse {
+    pub allowed: bool
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryChainDecimalsResponse {
+    pub decimals: Option<ChainDecimals>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryLivenessResponse {
+    pub last_heartbeat: Option<u64>,
+    pub timeout: u64,
+    pub emergency: bool,
+    pub processed_order: Option<Uint128>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryCheckpointResponse {
+    pub height: Option<u64>,
+    pub updated_at: Option<u64>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryOrderHashResponse {
+    pub order_hash: Option<Binary>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryDstOrderByHashResponse {
+    pub order_id: Option<Uint128>
+}
+
+// 目标链订单及来源链信息
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DstOrderResponse {
+    pub asset: String,
+    pub to: Addr,
+    pub amount: Uint128,
+    pub rewards: Uint128,
+    pub src_chain_id: Option<u64>,
+    pub src_tx_hash: Option<String>,
+    pub src_event_index: u32,
+    pub src_sender: Option<String>,
+    pub src_amount: Uint256,
+    pub order_hash: Option<Binary>
+}
+
+// 早于来源链信息记录的订单只有确认时间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryDstOrderResponse {
+    pub confirmed_at: Option<Uint128>,
+    // 是否存在发放失败待重试的转账
+    pub delivery_failed: bool,
+    pub order: Option<DstOrderResponse>
+}
+
+// 跨链交易返回的data，包含合约分配的订单id
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CrossChainResponse {
+    pub order_ids: Vec<Uint128>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryClientOrderResponse {
+    pub order_id: Option<Uint128>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryNonceResponse {
+    pub nonce: u64
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuerySignedOrderDigestResponse {
+    pub digest: Binary
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryRefundConfigResponse {
+    pub timeout: u64,
+    pub response_window: u64
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryRefundRequestResponse {
+    pub status: RefundRequestStatus,
+    pub requested_at: u64,
+    pub respond_before: u64,
+    pub proof: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryChainAddressFormatResponse {
+    pub format: Option<AddressFormat>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteResponse {
+    pub chain_id: u64,
+    pub remote_asset: String
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryRoutesResponse {
+    pub routes: Vec<RouteResponse>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryRetainedDustResponse {
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryWrappedSupplyResponse {
+    pub mode: AssetMode,
+    pub supply_cap: Option<Uint128>,
+    pub total_minted: Uint128,
+    pub total_burned: Uint128,
+    pub outstanding: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuerySwapPairResponse {
+    pub assets: Vec<String>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueryFailedDeliveriesResponse {
+    pub deliveries: Vec<FailedDeliveryResponse>
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct QueryConfigResponse {
     pub governor: Addr,
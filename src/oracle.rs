@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Decimal, QuerierWrapper, QueryRequest, StdResult, WasmQuery};
+
+// 价格预言机合约的查询消息
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    // 1个最小单位的base可兑换的quote最小单位数量
+    Price { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceResponse {
+    pub rate: Decimal
+}
+
+pub fn query_oracle_price(
+    querier: &QuerierWrapper,
+    oracle: Addr,
+    base: String,
+    quote: String,
+) -> StdResult<Decimal> {
+    let res: PriceResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: oracle.to_string(),
+        msg: to_binary(&OracleQueryMsg::Price { base, quote })?,
+    }))?;
+
+    Ok(res.rate)
+}
@@ -0,0 +1,89 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Decimal, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmQuery};
+
+// Terraswap/Astroport交易对使用的资产类型
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+impl AssetInfo {
+    // Coin为denom，Token为合约地址，与本合约的资产标识一致
+    pub fn to_asset(&self) -> String {
+        match self {
+            AssetInfo::Token { contract_addr } => contract_addr.clone(),
+            AssetInfo::NativeToken { denom } => denom.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128
+}
+
+// 交易对合约的执行消息，用于兑换Coin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairExecuteMsg {
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+// 交易对合约的cw20 Hook消息，用于兑换Token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairCw20HookMsg {
+    Swap {
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+// 交易对合约的查询消息
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairQueryMsg {
+    Pair {},
+    Simulation { offer_asset: Asset },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairInfo {
+    pub asset_infos: [AssetInfo; 2]
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128
+}
+
+pub fn query_pair_info(querier: &QuerierWrapper, pair: &Addr) -> StdResult<PairInfo> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: pair.to_string(),
+        msg: to_binary(&PairQueryMsg::Pair {})?,
+    }))
+}
+
+pub fn query_simulation(
+    querier: &QuerierWrapper,
+    pair: &Addr,
+    offer_asset: Asset,
+) -> StdResult<SimulationResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: pair.to_string(),
+        msg: to_binary(&PairQueryMsg::Simulation { offer_asset })?,
+    }))
+}
@@ -0,0 +1,138 @@
+use cosmwasm_std::{CanonicalAddr, Env, Uint128};
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::msg::SignedOrder;
+
+// 链下签名订单的编码，签名内容包含合约地址及链id，防止签名在其他合约或其他链上重放。
+// 按以下顺序逐字段拼接后取sha256作为摘要：
+//   "ibridge-signed-order-v1"（不带长度前缀）
+//   contract, chain_id, nonce, client_order_id, asset, from, to, amount, gas_fee,
+//   cross_chain_fee, rewards, src_chain_id, dst_chain_id, deadline, channel, fee_denom
+// 其中：
+//   string   4字节大端长度 + UTF-8内容
+//   u64      8字节大端
+//   Uint128  16字节大端
+//   Option   None为1字节0x00，Some为1字节0x01 + 内容
+const SIGNED_ORDER_DOMAIN: &[u8] = b"ibridge-signed-order-v1";
+
+fn put_str(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_uint128(buf: &mut Vec<u8>, value: Uint128) {
+    buf.extend_from_slice(&value.u128().to_be_bytes());
+}
+
+fn put_option<T>(buf: &mut Vec<u8>, value: &Option<T>, put: impl Fn(&mut Vec<u8>, &T)) {
+    match value {
+        Some(value) => {
+            buf.push(1);
+            put(buf, value);
+        },
+        None => buf.push(0),
+    }
+}
+
+// 签名内容的编码
+pub fn encode_signed_order(env: &Env, signed: &SignedOrder) -> Vec<u8> {
+    let order = &signed.order;
+    let mut buf: Vec<u8> = SIGNED_ORDER_DOMAIN.to_vec();
+    put_str(&mut buf, env.contract.address.as_str());
+    put_str(&mut buf, &env.block.chain_id);
+    put_u64(&mut buf, signed.nonce);
+    put_option(&mut buf, &order.client_order_id, |buf, value| put_uint128(buf, *value));
+    put_str(&mut buf, &order.asset);
+    put_str(&mut buf, &order.from);
+    put_str(&mut buf, &order.to);
+    put_uint128(&mut buf, order.amount);
+    put_uint128(&mut buf, order.gas_fee);
+    put_uint128(&mut buf, order.cross_chain_fee);
+    put_uint128(&mut buf, order.rewards);
+    put_u64(&mut buf, order.src_chain_id);
+    put_u64(&mut buf, order.dst_chain_id);
+    put_u64(&mut buf, order.deadline);
+    put_str(&mut buf, &order.channel);
+    put_option(&mut buf, &order.fee_denom, |buf, value| put_str(buf, value));
+    buf
+}
+
+// 链下签名订单的摘要
+pub fn signed_order_digest(env: &Env, signed: &SignedOrder) -> Vec<u8> {
+    Sha256::digest(&encode_signed_order(env, signed)).to_vec()
+}
+
+// 由33字节压缩公钥计算账户地址，即ripemd160(sha256(pubkey))
+pub fn pubkey_to_canonical(pubkey: &[u8]) -> Option<CanonicalAddr> {
+    if pubkey.len() != 33 {
+        return None;
+    }
+
+    Some(CanonicalAddr::from(Ripemd160::digest(&Sha256::digest(pubkey)).to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::Addr;
+    use crate::hash::to_hex;
+    use crate::msg::Order;
+
+    fn signed_order(fee_denom: Option<String>) -> SignedOrder {
+        SignedOrder {
+            order: Order {
+                client_order_id: Some(Uint128::from(42u128)),
+                asset: "uluna".to_string(),
+                from: "terra1sender".to_string(),
+                to: "0x000000000000000000000000000000000000dead".to_string(),
+                amount: Uint128::from(1000000u128),
+                gas_fee: Uint128::from(1000u128),
+                cross_chain_fee: Uint128::from(2000u128),
+                rewards: Uint128::zero(),
+                src_chain_id: 1,
+                dst_chain_id: 56,
+                deadline: 1700000000,
+                channel: "iswap".to_string(),
+                fee_denom
+            },
+            nonce: 7
+        }
+    }
+
+    fn signing_env() -> Env {
+        let mut env = mock_env();
+        env.contract.address = Addr::unchecked("terra1contract");
+        env.block.chain_id = "columbus-5".to_string();
+        env
+    }
+
+    #[test]
+    fn signed_order_digest_vector() {
+        let env = signing_env();
+        let signed = signed_order(None);
+
+        assert_eq!(encode_signed_order(&env, &signed).len(), 249);
+        assert_eq!(
+            to_hex(&signed_order_digest(&env, &signed)),
+            "0x09116a9b85480223204c84ec51697bf4cc5a4529c751852337db64007ae2bc66"
+        );
+    }
+
+    #[test]
+    fn signed_order_digest_binds_optional_fields() {
+        let env = signing_env();
+
+        // fee_denom为Some("")与None的编码不同
+        let none = encode_signed_order(&env, &signed_order(None));
+        let empty = encode_signed_order(&env, &signed_order(Some("".to_string())));
+        assert_eq!(none.last(), Some(&0u8));
+        assert_eq!(&empty[empty.len() - 5..], &[1u8, 0, 0, 0, 0]);
+        assert_ne!(signed_order_digest(&env, &signed_order(None)), signed_order_digest(&env, &signed_order(Some("".to_string()))));
+    }
+}
@@ -1,15 +1,66 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
+use cosmwasm_std::{Binary, CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128, Uint256};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
 
+use crate::msg::{
+    AddressFormat, AssetMode, ChainDecimals, DeliveryLeg, DeliveryMode, FeeTier, RefundRequestStatus, SwapRoute,
+    TokenCapability
+};
+
 pub static KEY_CONFIG: &[u8] = b"config";
 pub static PAUSE_FLAG: &[u8] = b"pause";
 pub static SUPPORT_TOKEN_CONFIG: &[u8] = b"support_token_config";
 pub static SUPPORT_COIN_CONFIG: &[u8] = b"support_coin_config";
 pub static SRC_ORDER_STATE: &[u8] = b"src_order_state";
 pub static DST_ORDER_STATE: &[u8] = b"dst_order_state";
+pub static ACCRUED_FEE: &[u8] = b"accrued_fee";
+pub static REWARDS_POOL: &[u8] = b"rewards_pool";
+pub static UNPAID_REWARDS: &[u8] = b"unpaid_rewards";
+pub static FEE_TIERS: &[u8] = b"fee_tiers";
+pub static CHANNEL_FEE_RATE: &[u8] = b"channel_fee_rate";
+pub static SENDER_VOLUME: &[u8] = b"sender_volume";
+pub static FEE_PRICE: &[u8] = b"fee_price";
+pub static PRICE_ORACLE: &[u8] = b"price_oracle";
+pub static FEE_BALANCE: &[u8] = b"fee_balance";
+pub static FEE_BALANCE_TOTAL: &[u8] = b"fee_balance_total";
+pub static TOKEN_CAPABILITY: &[u8] = b"token_capability";
+pub static LP_POOL: &[u8] = b"lp_pool";
+pub static LP_TOKEN_ASSET: &[u8] = b"lp_token_asset";
+pub static LP_ALLOWLIST: &[u8] = b"lp_allowlist";
+pub static DELIVERY_QUEUE: &[u8] = b"delivery_queue";
+pub static DELIVERY_QUEUE_ITEM: &[u8] = b"delivery_queue_item";
+pub static CLAIMABLE: &[u8] = b"claimable";
+pub static CLAIMABLE_TOTAL: &[u8] = b"claimable_total";
+pub static REPLY_ID: &[u8] = b"reply_id";
+pub static DELIVERY_REPLY: &[u8] = b"delivery_reply";
+pub static BATCH_REPLY: &[u8] = b"batch_reply";
+pub static FAILED_DELIVERY: &[u8] = b"failed_delivery";
+pub static FAILED_DELIVERY_TOTAL: &[u8] = b"failed_delivery_total";
+pub static DST_DELIVERY_FAILED: &[u8] = b"dst_delivery_failed";
+pub static CALL_ALLOWLIST: &[u8] = b"call_allowlist";
+pub static SWAP_PAIR: &[u8] = b"swap_pair";
+pub static ASSET_MODE: &[u8] = b"asset_mode";
+pub static WRAPPED_SUPPLY: &[u8] = b"wrapped_supply";
+pub static CHAIN_DECIMALS: &[u8] = b"chain_decimals";
+pub static RETAINED_DUST: &[u8] = b"retained_dust";
+pub static ROUTE: &[u8] = b"route";
+pub static CHAIN_ADDRESS_FORMAT: &[u8] = b"chain_address_format";
+pub static SRC_ORDER: &[u8] = b"src_order";
+pub static REFUND_CONFIG: &[u8] = b"refund_config";
+pub static REFUND_REQUEST: &[u8] = b"refund_request";
+pub static HEARTBEAT_TIMEOUT: &[u8] = b"heartbeat_timeout";
+pub static LAST_HEARTBEAT: &[u8] = b"last_heartbeat";
+pub static PROCESSED_ORDER: &[u8] = b"processed_order";
+pub static CHECKPOINT: &[u8] = b"checkpoint";
+pub static NONCE: &[u8] = b"nonce";
+pub static ORDER_ID: &[u8] = b"order_id";
+pub static CLIENT_ORDER: &[u8] = b"client_order";
+pub static SRC_ORDER_HASH: &[u8] = b"src_order_hash";
+pub static DST_ORDER_HASH: &[u8] = b"dst_order_hash";
+pub static DST_ORDER: &[u8] = b"dst_order";
+pub static DST_SRC_TX: &[u8] = b"dst_src_tx";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -20,6 +71,134 @@ pub struct Config {
     pub relayer: CanonicalAddr
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnpaidRewards {
+    pub asset: String,
+    pub to: CanonicalAddr,
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DailyVolume {
+    pub day: u64,
+    pub amount: Uint128
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpPool {
+    pub lp_token: CanonicalAddr,
+    pub total_share: Uint128,
+    pub liquidity: Uint128,
+    pub fee_share: Uint128,
+    pub permissionless: bool
+}
+
+// 底仓不足时等待发放的目标链订单
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDelivery {
+    pub order_id: Uint128,
+    pub asset: String,
+    pub to: CanonicalAddr,
+    pub amount: Uint128,
+    pub rewards: Uint128,
+    pub delivery_mode: DeliveryMode,
+    pub payload: Option<Binary>,
+    pub swap: Option<SwapRoute>
+}
+
+// 每个资产的待发放队列，head为队首序号，tail为下一个入队序号
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct DeliveryQueue {
+    pub head: u64,
+    pub tail: u64,
+    pub total_pending: Uint128
+}
+
+impl DeliveryQueue {
+    pub fn is_empty(&self) -> bool {
+        self.head == self.tail
+    }
+}
+
+// 铸造模式Token的铸造上限及累计铸造、销毁数量
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct WrappedSupply {
+    pub supply_cap: Option<Uint128>,
+    pub total_minted: Uint128,
+    pub total_burned: Uint128
+}
+
+impl WrappedSupply {
+    // 流通量，合约成为minter前已存在的供应量被销毁时不计为负
+    pub fn outstanding(&self) -> Uint128 {
+        self.total_minted.saturating_sub(self.total_burned)
+    }
+}
+
+// 源链订单的退款信息
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SrcOrder {
+    pub asset: String,
+    pub from: CanonicalAddr,
+    pub amount: Uint128,
+    pub gas_fee: Uint128,
+    pub deadline: u64,
+    // 铸造模式下跨出时销毁的数量，退款时按此数量重新铸造
+    #[serde(default)]
+    pub burned: Uint128
+}
+
+// 目标链订单及来源链信息，用于追溯来源链交易
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DstOrder {
+    pub asset: String,
+    pub to: CanonicalAddr,
+    pub amount: Uint128,
+    pub rewards: Uint128,
+    pub src_chain_id: Option<u64>,
+    pub src_tx_hash: Option<String>,
+    pub src_event_index: u32,
+    pub src_sender: Option<String>,
+    pub src_amount: Uint256,
+    pub order_hash: Option<Binary>
+}
+
+// 用户申请退款的等待时间及relayer答复的时间窗口
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundConfig {
+    pub timeout: u64,
+    pub response_window: u64
+}
+
+// 用户的退款申请
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundRequest {
+    pub status: RefundRequestStatus,
+    pub requested_at: u64,
+    pub proof: Option<String>
+}
+
+// relayer已处理到的源链区块高度及更新时间
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChainCheckpoint {
+    pub height: u64,
+    pub updated_at: u64
+}
+
+// 以SubMsg发出的单笔转账，用于回调时记录失败并重试
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Delivery {
+    pub order_id: Uint128,
+    pub leg: DeliveryLeg,
+    pub asset: String,
+    pub to: CanonicalAddr,
+    pub amount: Uint128,
+    // 接收方合约的调用参数，为空时直接转账
+    pub payload: Option<Binary>,
+    // 发放前的兑换路径，为空时发放原资产
+    pub swap: Option<SwapRoute>
+}
+
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
     singleton(storage, KEY_CONFIG).save(config)
 }
@@ -103,3 +282,888 @@ pub fn read_dst_order_state(
 ) -> StdResult<Option<Uint128>> {
     ReadonlyBucket::new(storage, DST_ORDER_STATE).may_load(&order_id.u128().to_be_bytes())
 }
+
+// 存储累计未划转的手续费
+pub fn store_accrued_fee(
+    storage: &mut dyn Storage,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, ACCRUED_FEE).save(asset.as_bytes(), amount)
+}
+
+// 读取累计未划转的手续费
+pub fn read_accrued_fee(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, ACCRUED_FEE).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储返佣资金池余额
+pub fn store_rewards_pool(
+    storage: &mut dyn Storage,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, REWARDS_POOL).save(asset.as_bytes(), amount)
+}
+
+// 读取返佣资金池余额
+pub fn read_rewards_pool(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, REWARDS_POOL).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储目标链订单未支付的返佣
+pub fn store_unpaid_rewards(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    unpaid: &UnpaidRewards
+) -> StdResult<()> {
+    Bucket::new(storage, UNPAID_REWARDS).save(&order_id.u128().to_be_bytes(), unpaid)
+}
+
+// 删除目标链订单未支付的返佣
+pub fn remove_unpaid_rewards(
+    storage: &mut dyn Storage,
+    order_id: &Uint128
+) {
+    Bucket::<UnpaidRewards>::new(storage, UNPAID_REWARDS).remove(&order_id.u128().to_be_bytes())
+}
+
+// 读取目标链订单未支付的返佣
+pub fn read_unpaid_rewards(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<Option<UnpaidRewards>> {
+    ReadonlyBucket::new(storage, UNPAID_REWARDS).may_load(&order_id.u128().to_be_bytes())
+}
+
+// 存储手续费分级配置
+pub fn store_fee_tiers(
+    storage: &mut dyn Storage,
+    asset: &String,
+    tiers: &[FeeTier]
+) -> StdResult<()> {
+    Bucket::new(storage, FEE_TIERS).save(asset.as_bytes(), &tiers.to_vec())
+}
+
+// 读取手续费分级配置
+pub fn read_fee_tiers(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Vec<FeeTier>> {
+    Ok(ReadonlyBucket::new(storage, FEE_TIERS).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储渠道商手续费率
+pub fn store_channel_fee_rate(
+    storage: &mut dyn Storage,
+    channel: &String,
+    fee_rate: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, CHANNEL_FEE_RATE).save(channel.as_bytes(), fee_rate)
+}
+
+// 删除渠道商手续费率
+pub fn remove_channel_fee_rate(
+    storage: &mut dyn Storage,
+    channel: &String
+) {
+    Bucket::<Uint128>::new(storage, CHANNEL_FEE_RATE).remove(channel.as_bytes())
+}
+
+// 读取渠道商手续费率
+pub fn read_channel_fee_rate(
+    storage: &dyn Storage,
+    channel: &String
+) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::new(storage, CHANNEL_FEE_RATE).may_load(channel.as_bytes())
+}
+
+// 存储用户按天统计的跨链量
+pub fn store_sender_volume(
+    storage: &mut dyn Storage,
+    sender: &CanonicalAddr,
+    asset: &String,
+    volumes: &[DailyVolume]
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[SENDER_VOLUME, sender.as_slice()]).save(asset.as_bytes(), &volumes.to_vec())
+}
+
+// 读取用户按天统计的跨链量
+pub fn read_sender_volume(
+    storage: &dyn Storage,
+    sender: &CanonicalAddr,
+    asset: &String
+) -> StdResult<Vec<DailyVolume>> {
+    Ok(ReadonlyBucket::multilevel(storage, &[SENDER_VOLUME, sender.as_slice()]).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储手续费币种价格
+pub fn store_fee_price(
+    storage: &mut dyn Storage,
+    asset: &String,
+    denom: &String,
+    price: &Decimal
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[FEE_PRICE, asset.as_bytes()]).save(denom.as_bytes(), price)
+}
+
+// 删除手续费币种价格
+pub fn remove_fee_price(
+    storage: &mut dyn Storage,
+    asset: &String,
+    denom: &String
+) {
+    Bucket::<Decimal>::multilevel(storage, &[FEE_PRICE, asset.as_bytes()]).remove(denom.as_bytes())
+}
+
+// 读取手续费币种价格
+pub fn read_fee_price(
+    storage: &dyn Storage,
+    asset: &String,
+    denom: &String
+) -> StdResult<Option<Decimal>> {
+    ReadonlyBucket::multilevel(storage, &[FEE_PRICE, asset.as_bytes()]).may_load(denom.as_bytes())
+}
+
+pub fn store_price_oracle(storage: &mut dyn Storage, oracle: &Option<CanonicalAddr>) -> StdResult<()> {
+    singleton(storage, PRICE_ORACLE).save(oracle)
+}
+
+pub fn read_price_oracle(storage: &dyn Storage) -> StdResult<Option<CanonicalAddr>> {
+    Ok(singleton_read(storage, PRICE_ORACLE).may_load()?.flatten())
+}
+
+// 存储用户预存的手续费余额
+pub fn store_fee_balance(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    denom: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[FEE_BALANCE, owner.as_slice()]).save(denom.as_bytes(), amount)
+}
+
+// 读取用户预存的手续费余额
+pub fn read_fee_balance(
+    storage: &dyn Storage,
+    owner: &CanonicalAddr,
+    denom: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::multilevel(storage, &[FEE_BALANCE, owner.as_slice()]).may_load(denom.as_bytes())?.unwrap_or_default())
+}
+
+// 存储所有用户预存的手续费余额总和
+pub fn store_fee_balance_total(
+    storage: &mut dyn Storage,
+    denom: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, FEE_BALANCE_TOTAL).save(denom.as_bytes(), amount)
+}
+
+// 读取所有用户预存的手续费余额总和
+pub fn read_fee_balance_total(
+    storage: &dyn Storage,
+    denom: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, FEE_BALANCE_TOTAL).may_load(denom.as_bytes())?.unwrap_or_default())
+}
+
+// 存储Token转账特性
+pub fn store_token_capability(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    capability: &TokenCapability
+) -> StdResult<()> {
+    Bucket::new(storage, TOKEN_CAPABILITY).save(token.as_slice(), capability)
+}
+
+// 读取Token转账特性，未设置时视为标准Token
+pub fn read_token_capability(
+    storage: &dyn Storage,
+    token: &CanonicalAddr
+) -> StdResult<TokenCapability> {
+    Ok(ReadonlyBucket::new(storage, TOKEN_CAPABILITY).may_load(token.as_slice())?.unwrap_or(TokenCapability::Standard))
+}
+
+// 存储资产的LP资金池
+pub fn store_lp_pool(
+    storage: &mut dyn Storage,
+    asset: &String,
+    pool: &LpPool
+) -> StdResult<()> {
+    Bucket::new(storage, LP_POOL).save(asset.as_bytes(), pool)
+}
+
+// 读取资产的LP资金池
+pub fn read_lp_pool(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Option<LpPool>> {
+    ReadonlyBucket::new(storage, LP_POOL).may_load(asset.as_bytes())
+}
+
+// 存储LP Token对应的资产
+pub fn store_lp_token_asset(
+    storage: &mut dyn Storage,
+    lp_token: &CanonicalAddr,
+    asset: &String
+) -> StdResult<()> {
+    Bucket::new(storage, LP_TOKEN_ASSET).save(lp_token.as_slice(), asset)
+}
+
+// 读取LP Token对应的资产
+pub fn read_lp_token_asset(
+    storage: &dyn Storage,
+    lp_token: &CanonicalAddr
+) -> StdResult<Option<String>> {
+    ReadonlyBucket::new(storage, LP_TOKEN_ASSET).may_load(lp_token.as_slice())
+}
+
+// 存储LP白名单
+pub fn store_lp_allowlist(
+    storage: &mut dyn Storage,
+    asset: &String,
+    provider: &CanonicalAddr,
+    allowed: bool
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[LP_ALLOWLIST, asset.as_bytes()]).save(provider.as_slice(), &allowed)
+}
+
+// 读取LP白名单
+pub fn read_lp_allowlist(
+    storage: &dyn Storage,
+    asset: &String,
+    provider: &CanonicalAddr
+) -> StdResult<bool> {
+    Ok(ReadonlyBucket::multilevel(storage, &[LP_ALLOWLIST, asset.as_bytes()]).may_load(provider.as_slice())?.unwrap_or(false))
+}
+
+// 存储待发放队列
+pub fn store_delivery_queue(
+    storage: &mut dyn Storage,
+    asset: &String,
+    queue: &DeliveryQueue
+) -> StdResult<()> {
+    Bucket::new(storage, DELIVERY_QUEUE).save(asset.as_bytes(), queue)
+}
+
+// 读取待发放队列
+pub fn read_delivery_queue(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<DeliveryQueue> {
+    Ok(ReadonlyBucket::new(storage, DELIVERY_QUEUE).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储待发放订单
+pub fn store_pending_delivery(
+    storage: &mut dyn Storage,
+    asset: &String,
+    index: u64,
+    delivery: &PendingDelivery
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[DELIVERY_QUEUE_ITEM, asset.as_bytes()]).save(&index.to_be_bytes(), delivery)
+}
+
+// 删除待发放订单
+pub fn remove_pending_delivery(
+    storage: &mut dyn Storage,
+    asset: &String,
+    index: u64
+) {
+    Bucket::<PendingDelivery>::multilevel(storage, &[DELIVERY_QUEUE_ITEM, asset.as_bytes()]).remove(&index.to_be_bytes())
+}
+
+// 读取待发放订单
+pub fn read_pending_delivery(
+    storage: &dyn Storage,
+    asset: &String,
+    index: u64
+) -> StdResult<Option<PendingDelivery>> {
+    ReadonlyBucket::multilevel(storage, &[DELIVERY_QUEUE_ITEM, asset.as_bytes()]).may_load(&index.to_be_bytes())
+}
+
+// 存储接收方的待领取余额
+pub fn store_claimable(
+    storage: &mut dyn Storage,
+    owner: &CanonicalAddr,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[CLAIMABLE, owner.as_slice()]).save(asset.as_bytes(), amount)
+}
+
+// 读取接收方的待领取余额
+pub fn read_claimable(
+    storage: &dyn Storage,
+    owner: &CanonicalAddr,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::multilevel(storage, &[CLAIMABLE, owner.as_slice()]).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储所有接收方的待领取余额总和
+pub fn store_claimable_total(
+    storage: &mut dyn Storage,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, CLAIMABLE_TOTAL).save(asset.as_bytes(), amount)
+}
+
+// 读取所有接收方的待领取余额总和
+pub fn read_claimable_total(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, CLAIMABLE_TOTAL).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 生成下一个SubMsg回调id
+pub fn next_reply_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = singleton_read(storage, REPLY_ID).may_load()?.unwrap_or(0u64) + 1;
+    singleton(storage, REPLY_ID).save(&id)?;
+    Ok(id)
+}
+
+// 存储SubMsg回调对应的发放信息
+pub fn store_delivery_reply(
+    storage: &mut dyn Storage,
+    id: u64,
+    delivery: &Delivery
+) -> StdResult<()> {
+    Bucket::new(storage, DELIVERY_REPLY).save(&id.to_be_bytes(), delivery)
+}
+
+// 删除SubMsg回调对应的发放信息
+pub fn remove_delivery_reply(
+    storage: &mut dyn Storage,
+    id: u64
+) {
+    Bucket::<Delivery>::new(storage, DELIVERY_REPLY).remove(&id.to_be_bytes())
+}
+
+// 读取SubMsg回调对应的发放信息
+pub fn read_delivery_reply(
+    storage: &dyn Storage,
+    id: u64
+) -> StdResult<Option<Delivery>> {
+    ReadonlyBucket::new(storage, DELIVERY_REPLY).may_load(&id.to_be_bytes())
+}
+
+// 存储批量汇总转账SubMsg回调对应的各笔发放
+pub fn store_batch_reply(
+    storage: &mut dyn Storage,
+    id: u64,
+    deliveries: &[Delivery]
+) -> StdResult<()> {
+    Bucket::new(storage, BATCH_REPLY).save(&id.to_be_bytes(), &deliveries.to_vec())
+}
+
+// 删除批量汇总转账SubMsg回调对应的各笔发放
+pub fn remove_batch_reply(
+    storage: &mut dyn Storage,
+    id: u64
+) {
+    Bucket::<Vec<Delivery>>::new(storage, BATCH_REPLY).remove(&id.to_be_bytes())
+}
+
+// 读取批量汇总转账SubMsg回调对应的各笔发放
+pub fn read_batch_reply(
+    storage: &dyn Storage,
+    id: u64
+) -> StdResult<Option<Vec<Delivery>>> {
+    ReadonlyBucket::new(storage, BATCH_REPLY).may_load(&id.to_be_bytes())
+}
+
+// 存储发放失败的转账
+pub fn store_failed_delivery(
+    storage: &mut dyn Storage,
+    delivery: &Delivery
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[FAILED_DELIVERY, &delivery.order_id.u128().to_be_bytes()])
+        .save(delivery.leg.as_str().as_bytes(), delivery)
+}
+
+// 删除发放失败的转账
+pub fn remove_failed_delivery(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    leg: &DeliveryLeg
+) {
+    Bucket::<Delivery>::multilevel(storage, &[FAILED_DELIVERY, &order_id.u128().to_be_bytes()])
+        .remove(leg.as_str().as_bytes())
+}
+
+// 读取发放失败的转账
+pub fn read_failed_delivery(
+    storage: &dyn Storage,
+    order_id: &Uint128,
+    leg: &DeliveryLeg
+) -> StdResult<Option<Delivery>> {
+    ReadonlyBucket::multilevel(storage, &[FAILED_DELIVERY, &order_id.u128().to_be_bytes()])
+        .may_load(leg.as_str().as_bytes())
+}
+
+// 标记目标链订单存在发放失败的转账
+pub fn store_dst_delivery_failed(
+    storage: &mut dyn Storage,
+    order_id: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, DST_DELIVERY_FAILED).save(&order_id.u128().to_be_bytes(), &true)
+}
+
+// 清除目标链订单的发放失败标记
+pub fn remove_dst_delivery_failed(
+    storage: &mut dyn Storage,
+    order_id: &Uint128
+) {
+    Bucket::<bool>::new(storage, DST_DELIVERY_FAILED).remove(&order_id.u128().to_be_bytes())
+}
+
+// 读取目标链订单是否存在发放失败的转账
+pub fn read_dst_delivery_failed(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<bool> {
+    Ok(ReadonlyBucket::new(storage, DST_DELIVERY_FAILED).may_load(&order_id.u128().to_be_bytes())?.unwrap_or(false))
+}
+
+// 存储发放失败的转账总和
+pub fn store_failed_delivery_total(
+    storage: &mut dyn Storage,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, FAILED_DELIVERY_TOTAL).save(asset.as_bytes(), amount)
+}
+
+// 读取发放失败的转账总和
+pub fn read_failed_delivery_total(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, FAILED_DELIVERY_TOTAL).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储目标链订单可调用的合约
+pub fn store_call_allowlist(
+    storage: &mut dyn Storage,
+    contract: &CanonicalAddr,
+    allowed: bool
+) -> StdResult<()> {
+    Bucket::new(storage, CALL_ALLOWLIST).save(contract.as_slice(), &allowed)
+}
+
+// 读取合约是否可被目标链订单调用
+pub fn read_call_allowlist(
+    storage: &dyn Storage,
+    contract: &CanonicalAddr
+) -> StdResult<bool> {
+    Ok(ReadonlyBucket::new(storage, CALL_ALLOWLIST).may_load(contract.as_slice())?.unwrap_or(false))
+}
+
+// 存储已登记交易对的两种资产
+pub fn store_swap_pair(
+    storage: &mut dyn Storage,
+    pair: &CanonicalAddr,
+    assets: &[String; 2]
+) -> StdResult<()> {
+    Bucket::new(storage, SWAP_PAIR).save(pair.as_slice(), assets)
+}
+
+// 移除已登记的交易对
+pub fn remove_swap_pair(
+    storage: &mut dyn Storage,
+    pair: &CanonicalAddr
+) {
+    Bucket::<[String; 2]>::new(storage, SWAP_PAIR).remove(pair.as_slice())
+}
+
+// 读取已登记交易对的两种资产
+pub fn read_swap_pair(
+    storage: &dyn Storage,
+    pair: &CanonicalAddr
+) -> StdResult<Option<[String; 2]>> {
+    ReadonlyBucket::new(storage, SWAP_PAIR).may_load(pair.as_slice())
+}
+
+// 存储Token的跨链方式
+pub fn store_asset_mode(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    mode: &AssetMode
+) -> StdResult<()> {
+    Bucket::new(storage, ASSET_MODE).save(token.as_slice(), mode)
+}
+
+// 读取Token的跨链方式，未设置时为锁定释放
+pub fn read_asset_mode(
+    storage: &dyn Storage,
+    token: &CanonicalAddr
+) -> StdResult<AssetMode> {
+    Ok(ReadonlyBucket::new(storage, ASSET_MODE).may_load(token.as_slice())?.unwrap_or_default())
+}
+
+// 存储铸造模式Token的供应量
+pub fn store_wrapped_supply(
+    storage: &mut dyn Storage,
+    token: &CanonicalAddr,
+    supply: &WrappedSupply
+) -> StdResult<()> {
+    Bucket::new(storage, WRAPPED_SUPPLY).save(token.as_slice(), supply)
+}
+
+// 读取铸造模式Token的供应量
+pub fn read_wrapped_supply(
+    storage: &dyn Storage,
+    token: &CanonicalAddr
+) -> StdResult<WrappedSupply> {
+    Ok(ReadonlyBucket::new(storage, WRAPPED_SUPPLY).may_load(token.as_slice())?.unwrap_or_default())
+}
+
+// 存储资产在其他链上的精度
+pub fn store_chain_decimals(
+    storage: &mut dyn Storage,
+    asset: &String,
+    chain_id: u64,
+    decimals: &ChainDecimals
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[CHAIN_DECIMALS, asset.as_bytes()]).save(&chain_id.to_be_bytes(), decimals)
+}
+
+// 移除资产在其他链上的精度
+pub fn remove_chain_decimals(
+    storage: &mut dyn Storage,
+    asset: &String,
+    chain_id: u64
+) {
+    Bucket::<ChainDecimals>::multilevel(storage, &[CHAIN_DECIMALS, asset.as_bytes()]).remove(&chain_id.to_be_bytes())
+}
+
+// 读取资产在其他链上的精度，未设置时两链数量相同
+pub fn read_chain_decimals(
+    storage: &dyn Storage,
+    asset: &String,
+    chain_id: u64
+) -> StdResult<Option<ChainDecimals>> {
+    ReadonlyBucket::multilevel(storage, &[CHAIN_DECIMALS, asset.as_bytes()]).may_load(&chain_id.to_be_bytes())
+}
+
+// 存储精度换算留存的余数
+pub fn store_retained_dust(
+    storage: &mut dyn Storage,
+    asset: &String,
+    amount: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, RETAINED_DUST).save(asset.as_bytes(), amount)
+}
+
+// 读取精度换算留存的余数
+pub fn read_retained_dust(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Uint128> {
+    Ok(ReadonlyBucket::new(storage, RETAINED_DUST).may_load(asset.as_bytes())?.unwrap_or_default())
+}
+
+// 存储资产在目标链上对应的资产
+pub fn store_route(
+    storage: &mut dyn Storage,
+    asset: &String,
+    chain_id: u64,
+    remote_asset: &String
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[ROUTE, asset.as_bytes()]).save(&chain_id.to_be_bytes(), remote_asset)
+}
+
+// 移除资产在目标链上对应的资产
+pub fn remove_route(
+    storage: &mut dyn Storage,
+    asset: &String,
+    chain_id: u64
+) {
+    Bucket::<String>::multilevel(storage, &[ROUTE, asset.as_bytes()]).remove(&chain_id.to_be_bytes())
+}
+
+// 读取资产在目标链上对应的资产
+pub fn read_route(
+    storage: &dyn Storage,
+    asset: &String,
+    chain_id: u64
+) -> StdResult<Option<String>> {
+    ReadonlyBucket::multilevel(storage, &[ROUTE, asset.as_bytes()]).may_load(&chain_id.to_be_bytes())
+}
+
+// 读取资产在所有目标链上对应的资产，按链id排序
+pub fn read_routes(
+    storage: &dyn Storage,
+    asset: &String
+) -> StdResult<Vec<(u64, String)>> {
+    ReadonlyBucket::<String>::multilevel(storage, &[ROUTE, asset.as_bytes()])
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (key, remote_asset) = item?;
+            let mut chain_id = [0u8; 8];
+            chain_id.copy_from_slice(&key);
+            Ok((u64::from_be_bytes(chain_id), remote_asset))
+        })
+        .collect()
+}
+
+// 存储目标链的地址格式
+pub fn store_chain_address_format(
+    storage: &mut dyn Storage,
+    chain_id: u64,
+    format: &AddressFormat
+) -> StdResult<()> {
+    Bucket::new(storage, CHAIN_ADDRESS_FORMAT).save(&chain_id.to_be_bytes(), format)
+}
+
+// 移除目标链的地址格式
+pub fn remove_chain_address_format(
+    storage: &mut dyn Storage,
+    chain_id: u64
+) {
+    Bucket::<AddressFormat>::new(storage, CHAIN_ADDRESS_FORMAT).remove(&chain_id.to_be_bytes())
+}
+
+// 读取目标链的地址格式
+pub fn read_chain_address_format(
+    storage: &dyn Storage,
+    chain_id: u64
+) -> StdResult<Option<AddressFormat>> {
+    ReadonlyBucket::new(storage, CHAIN_ADDRESS_FORMAT).may_load(&chain_id.to_be_bytes())
+}
+
+// 存储源链订单的退款信息
+pub fn store_src_order(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    order: &SrcOrder
+) -> StdResult<()> {
+    Bucket::new(storage, SRC_ORDER).save(&order_id.u128().to_be_bytes(), order)
+}
+
+// 读取源链订单的退款信息
+pub fn read_src_order(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<Option<SrcOrder>> {
+    ReadonlyBucket::new(storage, SRC_ORDER).may_load(&order_id.u128().to_be_bytes())
+}
+
+pub fn store_refund_config(storage: &mut dyn Storage, config: &RefundConfig) -> StdResult<()> {
+    singleton(storage, REFUND_CONFIG).save(config)
+}
+
+pub fn read_refund_config(storage: &dyn Storage) -> StdResult<Option<RefundConfig>> {
+    singleton_read(storage, REFUND_CONFIG).may_load()
+}
+
+// 存储用户的退款申请
+pub fn store_refund_request(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    request: &RefundRequest
+) -> StdResult<()> {
+    Bucket::new(storage, REFUND_REQUEST).save(&order_id.u128().to_be_bytes(), request)
+}
+
+// 读取用户的退款申请
+pub fn read_refund_request(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<Option<RefundRequest>> {
+    ReadonlyBucket::new(storage, REFUND_REQUEST).may_load(&order_id.u128().to_be_bytes())
+}
+
+pub fn store_heartbeat_timeout(storage: &mut dyn Storage, timeout: &u64) -> StdResult<()> {
+    singleton(storage, HEARTBEAT_TIMEOUT).save(timeout)
+}
+
+pub fn read_heartbeat_timeout(storage: &dyn Storage) -> StdResult<Option<u64>> {
+    singleton_read(storage, HEARTBEAT_TIMEOUT).may_load()
+}
+
+pub fn store_last_heartbeat(storage: &mut dyn Storage, time: &u64) -> StdResult<()> {
+    singleton(storage, LAST_HEARTBEAT).save(time)
+}
+
+pub fn read_last_heartbeat(storage: &dyn Storage) -> StdResult<Option<u64>> {
+    singleton_read(storage, LAST_HEARTBEAT).may_load()
+}
+
+// 存储relayer已处理到的源链订单号
+pub fn store_processed_order(storage: &mut dyn Storage, order_id: &Uint128) -> StdResult<()> {
+    singleton(storage, PROCESSED_ORDER).save(order_id)
+}
+
+pub fn read_processed_order(storage: &dyn Storage) -> StdResult<Option<Uint128>> {
+    singleton_read(storage, PROCESSED_ORDER).may_load()
+}
+
+// 存储relayer已处理到的源链区块高度
+pub fn store_checkpoint(
+    storage: &mut dyn Storage,
+    chain_id: u64,
+    checkpoint: &ChainCheckpoint
+) -> StdResult<()> {
+    Bucket::new(storage, CHECKPOINT).save(&chain_id.to_be_bytes(), checkpoint)
+}
+
+// 读取relayer已处理到的源链区块高度
+pub fn read_checkpoint(
+    storage: &dyn Storage,
+    chain_id: u64
+) -> StdResult<Option<ChainCheckpoint>> {
+    ReadonlyBucket::new(storage, CHECKPOINT).may_load(&chain_id.to_be_bytes())
+}
+
+// 存储用户链下签名订单的nonce
+pub fn store_nonce(storage: &mut dyn Storage, owner: &CanonicalAddr, nonce: &u64) -> StdResult<()> {
+    Bucket::new(storage, NONCE).save(owner.as_slice(), nonce)
+}
+
+// 读取用户链下签名订单的nonce
+pub fn read_nonce(storage: &dyn Storage, owner: &CanonicalAddr) -> StdResult<u64> {
+    Ok(ReadonlyBucket::new(storage, NONCE).may_load(owner.as_slice())?.unwrap_or_default())
+}
+
+// 生成下一个源链订单id
+pub fn next_order_id(storage: &mut dyn Storage) -> StdResult<Uint128> {
+    let id = singleton_read(storage, ORDER_ID).may_load()?.unwrap_or_else(Uint128::zero).checked_add(Uint128::from(1u128))?;
+    singleton(storage, ORDER_ID).save(&id)?;
+    Ok(id)
+}
+
+// 存储发送方的幂等键对应的订单id
+pub fn store_client_order(
+    storage: &mut dyn Storage,
+    sender: &CanonicalAddr,
+    client_order_id: &Uint128,
+    order_id: &Uint128
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[CLIENT_ORDER, sender.as_slice()]).save(&client_order_id.u128().to_be_bytes(), order_id)
+}
+
+// 读取发送方的幂等键对应的订单id
+pub fn read_client_order(
+    storage: &dyn Storage,
+    sender: &CanonicalAddr,
+    client_order_id: &Uint128
+) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::multilevel(storage, &[CLIENT_ORDER, sender.as_slice()]).may_load(&client_order_id.u128().to_be_bytes())
+}
+
+// 存储源链订单的订单哈希
+pub fn store_src_order_hash(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    order_hash: &Binary
+) -> StdResult<()> {
+    Bucket::new(storage, SRC_ORDER_HASH).save(&order_id.u128().to_be_bytes(), order_hash)
+}
+
+// 读取源链订单的订单哈希
+pub fn read_src_order_hash(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<Option<Binary>> {
+    ReadonlyBucket::new(storage, SRC_ORDER_HASH).may_load(&order_id.u128().to_be_bytes())
+}
+
+// 存储来源链订单哈希对应的目标链订单id
+pub fn store_dst_order_hash(
+    storage: &mut dyn Storage,
+    order_hash: &Binary,
+    order_id: &Uint128
+) -> StdResult<()> {
+    Bucket::new(storage, DST_ORDER_HASH).save(order_hash.as_slice(), order_id)
+}
+
+// 读取来源链订单哈希对应的目标链订单id
+pub fn read_dst_order_hash(
+    storage: &dyn Storage,
+    order_hash: &Binary
+) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::new(storage, DST_ORDER_HASH).may_load(order_hash.as_slice())
+}
+
+// 存储目标链订单及来源链信息
+pub fn store_dst_order(
+    storage: &mut dyn Storage,
+    order_id: &Uint128,
+    order: &DstOrder
+) -> StdResult<()> {
+    Bucket::new(storage, DST_ORDER).save(&order_id.u128().to_be_bytes(), order)
+}
+
+// 读取目标链订单及来源链信息
+pub fn read_dst_order(
+    storage: &dyn Storage,
+    order_id: &Uint128
+) -> StdResult<Option<DstOrder>> {
+    ReadonlyBucket::new(storage, DST_ORDER).may_load(&order_id.u128().to_be_bytes())
+}
+
+// 十六进制交易哈希不区分大小写，无论是否带0x前缀（Cosmos、Tron的交易哈希不带前缀）统一转为小写；
+// base58等其他格式区分大小写，保持原样
+fn dst_src_tx_key(src_tx_hash: &str, src_event_index: u32) -> Vec<u8> {
+    let digits = src_tx_hash.strip_prefix("0x").or_else(|| src_tx_hash.strip_prefix("0X")).unwrap_or(src_tx_hash);
+    let mut key = if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        src_tx_hash.to_lowercase().into_bytes()
+    } else {
+        src_tx_hash.as_bytes().to_vec()
+    };
+    key.extend_from_slice(&src_event_index.to_be_bytes());
+    key
+}
+
+// 存储来源链交易中的订单对应的目标链订单id
+pub fn store_dst_src_tx(
+    storage: &mut dyn Storage,
+    src_chain_id: u64,
+    src_tx_hash: &str,
+    src_event_index: u32,
+    order_id: &Uint128
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[DST_SRC_TX, &src_chain_id.to_be_bytes()])
+        .save(&dst_src_tx_key(src_tx_hash, src_event_index), order_id)
+}
+
+// 读取来源链交易中的订单对应的目标链订单id
+pub fn read_dst_src_tx(
+    storage: &dyn Storage,
+    src_chain_id: u64,
+    src_tx_hash: &str,
+    src_event_index: u32
+) -> StdResult<Option<Uint128>> {
+    ReadonlyBucket::multilevel(storage, &[DST_SRC_TX, &src_chain_id.to_be_bytes()])
+        .may_load(&dst_src_tx_key(src_tx_hash, src_event_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dst_src_tx_key_normalizes_hex_only() {
+        assert_eq!(dst_src_tx_key("0xABCdef", 1), dst_src_tx_key("0xabcdef", 1));
+        assert_eq!(dst_src_tx_key("0XABCDEF", 1), dst_src_tx_key("0xabcdef", 1));
+        // Cosmos、Tron的十六进制交易哈希不带0x前缀
+        assert_eq!(
+            dst_src_tx_key("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855", 1),
+            dst_src_tx_key("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", 1)
+        );
+        // base58签名区分大小写
+        assert_ne!(dst_src_tx_key("5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb", 1), dst_src_tx_key("5verv8nmvzbjmekv8xnrlkeawrtsz9coskdyjcjjbrnb", 1));
+    }
+}
@@ -0,0 +1,118 @@
+use cosmwasm_std::{Coin, Decimal, QuerierWrapper, StdResult, Uint128};
+use terra_cosmwasm::TerraQuerier;
+
+const DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+// 查询Terra稳定费率及上限，测试时可替换为mock实现
+pub trait TaxQuerier {
+    fn query_tax_rate(&self) -> StdResult<Decimal>;
+    fn query_tax_cap(&self, denom: &str) -> StdResult<Uint128>;
+}
+
+impl TaxQuerier for QuerierWrapper<'_> {
+    fn query_tax_rate(&self) -> StdResult<Decimal> {
+        Ok(TerraQuerier::new(self).query_tax_rate()?.rate)
+    }
+
+    fn query_tax_cap(&self, denom: &str) -> StdResult<Uint128> {
+        Ok(TerraQuerier::new(self).query_tax_cap(denom)?.cap)
+    }
+}
+
+// 计算转出coin时需要扣除的稳定费，uluna不收取稳定费
+pub fn compute_tax(querier: &dyn TaxQuerier, coin: &Coin) -> StdResult<Uint128> {
+    if coin.denom == "uluna" || coin.amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let tax_rate = querier.query_tax_rate()?;
+    let tax_cap = querier.query_tax_cap(&coin.denom)?;
+
+    // 转出数量 + 稳定费 = coin.amount
+    let tax = coin.amount.checked_sub(coin.amount.multiply_ratio(
+        DECIMAL_FRACTION,
+        DECIMAL_FRACTION * tax_rate + DECIMAL_FRACTION,
+    ))?;
+
+    Ok(std::cmp::min(tax, tax_cap))
+}
+
+// 从转出数量中扣除稳定费，保证合约实际支出的数量等于coin.amount，返回扣税后的coin及稳定费
+pub fn deduct_tax(querier: &dyn TaxQuerier, coin: Coin) -> StdResult<(Coin, Uint128)> {
+    let tax = compute_tax(querier, &coin)?;
+
+    Ok((
+        Coin {
+            denom: coin.denom,
+            amount: coin.amount.checked_sub(tax)?,
+        },
+        tax,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 固定稳定费率及上限的mock实现
+    struct MockTaxQuerier {
+        rate: Decimal,
+        cap: Uint128,
+    }
+
+    impl TaxQuerier for MockTaxQuerier {
+        fn query_tax_rate(&self) -> StdResult<Decimal> {
+            Ok(self.rate)
+        }
+
+        fn query_tax_cap(&self, _denom: &str) -> StdResult<Uint128> {
+            Ok(self.cap)
+        }
+    }
+
+    fn coin(amount: u128, denom: &str) -> Coin {
+        Coin { denom: denom.to_string(), amount: Uint128::new(amount) }
+    }
+
+    #[test]
+    fn compute_tax_by_rate() {
+        let querier = MockTaxQuerier { rate: Decimal::permille(5), cap: Uint128::new(1_000_000) };
+
+        // 1_005_000 = 1_000_000 + 1_000_000 * 0.5%
+        assert_eq!(compute_tax(&querier, &coin(1_005_000, "uusd")).unwrap(), Uint128::new(5_000));
+    }
+
+    #[test]
+    fn compute_tax_clamped_by_cap() {
+        let querier = MockTaxQuerier { rate: Decimal::permille(5), cap: Uint128::new(1_000) };
+
+        assert_eq!(compute_tax(&querier, &coin(1_005_000, "uusd")).unwrap(), Uint128::new(1_000));
+    }
+
+    #[test]
+    fn compute_tax_uluna_and_zero_exempt() {
+        let querier = MockTaxQuerier { rate: Decimal::permille(5), cap: Uint128::new(1_000_000) };
+
+        assert_eq!(compute_tax(&querier, &coin(1_005_000, "uluna")).unwrap(), Uint128::zero());
+        assert_eq!(compute_tax(&querier, &coin(0, "uusd")).unwrap(), Uint128::zero());
+    }
+
+    #[test]
+    fn deduct_tax_rounding() {
+        let rate = Decimal::permille(3);
+        let querier = MockTaxQuerier { rate, cap: Uint128::new(1_000_000) };
+
+        for amount in [1u128, 999, 1_000, 1_003, 123_457, 10_000_001] {
+            let (sent, tax) = deduct_tax(&querier, coin(amount, "uusd")).unwrap();
+            // 转出数量与稳定费之和等于原数量
+            assert_eq!(sent.amount + tax, Uint128::new(amount));
+            // 链上按转出数量收取的稳定费不超过预留的稳定费，合约实际支出不超过原数量
+            assert!(sent.amount * rate <= tax);
+        }
+
+        // 1_000 / 1.003 = 997.008，向下取整后转出997，预留3
+        let (sent, tax) = deduct_tax(&querier, coin(1_000, "uusd")).unwrap();
+        assert_eq!(sent.amount, Uint128::new(997));
+        assert_eq!(tax, Uint128::new(3));
+    }
+}